@@ -0,0 +1,51 @@
+use crate::error::CliError;
+use crate::is_stdio;
+use clap::ValueEnum;
+use qsc_core::qrcode::EcLevel;
+use qsc_core::{Compression, parse_ec_level};
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum Framing {
+    #[default]
+    Chunked,
+    Fountain,
+    StructuredAppend,
+}
+
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum CompressionArg {
+    #[default]
+    Auto,
+    Deflate,
+    None,
+}
+
+impl From<CompressionArg> for Compression {
+    fn from(value: CompressionArg) -> Self {
+        match value {
+            CompressionArg::Auto => Compression::Auto,
+            CompressionArg::Deflate => Compression::Deflate,
+            CompressionArg::None => Compression::None,
+        }
+    }
+}
+
+pub fn parse_ec_level_arg(value: &str) -> Result<EcLevel, String> {
+    parse_ec_level(value).ok_or_else(|| format!("unknown error correction level '{}'", value))
+}
+
+pub fn read_input(path: &Path) -> Result<Vec<u8>, CliError> {
+    if !is_stdio(path) {
+        return fs::read(path).map_err(|e| CliError::io(path, e));
+    }
+
+    let mut data = Vec::new();
+    io::stdin()
+        .lock()
+        .read_to_end(&mut data)
+        .map_err(|e| CliError::io("standard input", e))?;
+    Ok(data)
+}
@@ -0,0 +1,272 @@
+use crate::error::CliError;
+use crate::is_stdio;
+use crate::passphrase::PassphraseArgs;
+use clap::Args;
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::{AnimationDecoder, DynamicImage, Frames, ImageFormat, ImageReader};
+use qsc_core::{PushOutcome, Reassembler, ScannedCode, join_structured_append, scan_luma};
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+const IMAGE_EXTENSIONS: [&str; 5] = ["png", "apng", "jpg", "jpeg", "gif"];
+
+#[derive(Debug, Args)]
+pub struct DecodeArgs {
+    #[arg(
+        required = true,
+        help = "PNG, JPEG or animated GIF/APNG images of the frames, or directories containing them"
+    )]
+    inputs: Vec<PathBuf>,
+
+    #[arg(
+        short,
+        long,
+        help = "File to write the decoded data to; standard output when omitted or -"
+    )]
+    output: Option<PathBuf>,
+
+    #[command(flatten)]
+    passphrase: PassphraseArgs,
+}
+
+enum Source {
+    Still(DynamicImage),
+    Animation(Frames<'static>),
+}
+
+pub fn run(args: DecodeArgs) -> Result<(), CliError> {
+    let output = args.output.as_deref().filter(|path| !is_stdio(path));
+    if output.is_none() && io::stdout().is_terminal() {
+        return Err(CliError::InvalidArgument(
+            "refusing to write decoded data to a terminal, pass --output or redirect stdout"
+                .to_string(),
+        ));
+    }
+
+    let files = collect_images(&args.inputs)?;
+    let passphrase = args.passphrase.read()?;
+    let mut collector = Collector::new(passphrase.as_deref());
+
+    for path in &files {
+        match open_source(path)? {
+            Source::Still(image) => {
+                let codes = scan(image);
+                let found = codes.len();
+                let new = collector.add(path, codes);
+                eprintln!("{}: {} codes, {} new", path.display(), found, new);
+            }
+            Source::Animation(frames) => {
+                let (mut count, mut new) = (0, 0);
+                let mut found = Vec::new();
+                for (index, frame) in frames.enumerate() {
+                    let frame = frame.map_err(|e| CliError::image(path, e))?;
+                    let codes = scan(DynamicImage::ImageRgba8(frame.into_buffer()));
+                    if !codes.is_empty() {
+                        found.push(index as u32);
+                    }
+                    new += collector.add(path, codes);
+                    count += 1;
+                }
+                eprintln!(
+                    "{}: {} frames, codes in frames {}, {} new",
+                    path.display(),
+                    count,
+                    format_indices(&found),
+                    new
+                );
+            }
+        }
+    }
+
+    let data = collector.finish()?;
+    match output {
+        Some(path) => {
+            fs::write(path, &data).map_err(|e| CliError::io(path, e))?;
+            eprintln!("Wrote {} bytes to {}", data.len(), path.display());
+        }
+        None => {
+            let mut stdout = io::stdout().lock();
+            stdout
+                .write_all(&data)
+                .and_then(|()| stdout.flush())
+                .map_err(|e| CliError::io("standard output", e))?;
+            eprintln!("Wrote {} bytes to standard output", data.len());
+        }
+    }
+    Ok(())
+}
+
+// GIFs and PNGs carrying an animation control chunk are read frame by frame;
+// the format is sniffed from the content, not the extension.
+fn open_source(path: &Path) -> Result<Source, CliError> {
+    let reader = ImageReader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|e| CliError::io(path, e))?;
+    let image_error = |e| CliError::image(path, e);
+
+    match reader.format() {
+        Some(ImageFormat::Gif) => {
+            let decoder = GifDecoder::new(reader.into_inner()).map_err(image_error)?;
+            Ok(Source::Animation(decoder.into_frames()))
+        }
+        Some(ImageFormat::Png) => {
+            let decoder = PngDecoder::new(reader.into_inner()).map_err(image_error)?;
+            if decoder.is_apng().map_err(image_error)? {
+                let frames = decoder.apng().map_err(image_error)?.into_frames();
+                Ok(Source::Animation(frames))
+            } else {
+                let image = DynamicImage::from_decoder(decoder).map_err(image_error)?;
+                Ok(Source::Still(image))
+            }
+        }
+        _ => Ok(Source::Still(reader.decode().map_err(image_error)?)),
+    }
+}
+
+// Transparent pixels count as the light background they are shown on.
+fn scan(image: DynamicImage) -> Vec<ScannedCode> {
+    let image = image.into_luma_alpha8();
+    let pixels: Vec<u8> = image
+        .pixels()
+        .map(|pixel| {
+            let [luma, alpha] = pixel.0;
+            ((luma as u32 * alpha as u32 + 255 * (255 - alpha as u32)) / 255) as u8
+        })
+        .collect();
+    scan_luma(&pixels, image.width(), image.height())
+}
+
+// "0-3, 5, 7-9"
+fn format_indices(indices: &[u32]) -> String {
+    if indices.is_empty() {
+        return "none".to_string();
+    }
+
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    for index in indices {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == *index => *end = *index,
+            _ => ranges.push((*index, *index)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{}-{}", start, end)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// Directories contribute their images in file name order, which matches the
+// order `qsc encode` writes frames in; files named explicitly are always read.
+fn collect_images(inputs: &[PathBuf]) -> Result<Vec<PathBuf>, CliError> {
+    let mut files = Vec::new();
+    for input in inputs {
+        if !input.is_dir() {
+            files.push(input.clone());
+            continue;
+        }
+
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(input).map_err(|e| CliError::io(input, e))? {
+            let path = entry.map_err(|e| CliError::io(input, e))?.path();
+            if path.is_file() && is_image(&path) {
+                entries.push(path);
+            }
+        }
+        entries.sort();
+        files.extend(entries);
+    }
+
+    if files.is_empty() {
+        return Err(CliError::InvalidArgument(
+            "no PNG, APNG, JPEG or GIF images found".to_string(),
+        ));
+    }
+    Ok(files)
+}
+
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            IMAGE_EXTENSIONS
+                .iter()
+                .any(|known| extension.eq_ignore_ascii_case(known))
+        })
+}
+
+// Frames of a qsc sequence go to the reassembler; symbols carrying a
+// Structured Append header are joined separately at the end.
+struct Collector {
+    reassembler: Reassembler,
+    frames: usize,
+    structured_append: Vec<ScannedCode>,
+}
+
+impl Collector {
+    fn new(passphrase: Option<&str>) -> Self {
+        let reassembler = match passphrase {
+            Some(passphrase) => Reassembler::new().with_passphrase(passphrase),
+            None => Reassembler::new(),
+        };
+        Self {
+            reassembler,
+            frames: 0,
+            structured_append: Vec::new(),
+        }
+    }
+
+    // Returns how many of the codes had not been seen before.
+    fn add(&mut self, source: &Path, codes: Vec<ScannedCode>) -> usize {
+        let mut new = 0;
+        for code in codes {
+            if let Some(header) = code.structured_append {
+                let seen = self.structured_append.iter().any(|known| {
+                    known
+                        .structured_append
+                        .is_some_and(|known| known.index == header.index)
+                });
+                if !seen {
+                    new += 1;
+                }
+                self.structured_append.push(code);
+                continue;
+            }
+
+            match self.reassembler.push(&code.data) {
+                Ok(PushOutcome::Accepted) => {
+                    self.frames += 1;
+                    new += 1;
+                }
+                Ok(PushOutcome::Duplicate) => {}
+                Err(e) => eprintln!("warning: {}: skipped a QR code: {}", source.display(), e),
+            }
+        }
+        new
+    }
+
+    fn finish(self) -> Result<Vec<u8>, CliError> {
+        if self.frames == 0 && !self.structured_append.is_empty() {
+            return Ok(join_structured_append(&self.structured_append)?);
+        }
+        if self.frames == 0 {
+            return Err(CliError::InvalidArgument(
+                "no QR codes of a qsc sequence found".to_string(),
+            ));
+        }
+
+        let missing = self.reassembler.missing_indices();
+        if !missing.is_empty() {
+            eprintln!("missing blocks: {}", format_indices(&missing));
+        }
+        Ok(self.reassembler.finish()?)
+    }
+}
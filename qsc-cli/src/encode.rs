@@ -0,0 +1,294 @@
+use crate::args::{CompressionArg, Framing, parse_ec_level_arg, read_input};
+use crate::error::CliError;
+use crate::is_stdio;
+use crate::passphrase::PassphraseArgs;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Args, ValueEnum};
+use qsc_core::qrcode::{EcLevel, Version};
+use qsc_core::{
+    DEFAULT_QR_CODE_VERSION, DEFAULT_QUIET_ZONE, EncodeOptions, Encoder, MAX_SEQUENCE_FRAMES,
+    Matrix, Symbol, TransferMode, encode_structured_append, frame_file_name, render_png,
+    render_svg, validate_version,
+};
+use std::borrow::Cow;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_OUTPUT_DIR: &str = "qsc-frames";
+// Every name `frame_file_name` produces starts with this.
+const FRAME_FILE_PREFIX: &str = "frame-";
+const DEFAULT_MODULE_SIZE: u32 = 4;
+const DEFAULT_FOUNTAIN_PASSES: u64 = 2;
+
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum ImageFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Svg => "svg",
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct EncodeArgs {
+    #[arg(help = "File to encode, or - for standard input")]
+    file: PathBuf,
+
+    #[arg(short, long, default_value = DEFAULT_OUTPUT_DIR, help = "Directory for the frame images")]
+    output: PathBuf,
+
+    #[arg(
+        long,
+        help = "Write into a non-empty output directory, removing its old frame images"
+    )]
+    force: bool,
+
+    #[arg(
+        short,
+        long,
+        value_enum,
+        default_value_t,
+        help = "Image format of each frame"
+    )]
+    format: ImageFormat,
+
+    #[arg(long, default_value_t = DEFAULT_QR_CODE_VERSION, help = "QR version, 1 to 40")]
+    version: i16,
+
+    #[arg(short, long, default_value = "L", value_parser = parse_ec_level_arg, help = "Error correction level: L, M, Q or H")]
+    ec_level: EcLevel,
+
+    #[arg(
+        short,
+        long,
+        default_value_t = DEFAULT_MODULE_SIZE,
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Pixels per module for PNG, user units per module for SVG"
+    )]
+    module_size: u32,
+
+    #[arg(short, long, default_value_t = DEFAULT_QUIET_ZONE, help = "Light border in modules")]
+    quiet_zone: u32,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t,
+        help = "How the file is split over QR codes"
+    )]
+    framing: Framing,
+
+    #[arg(
+        long,
+        default_value_t = DEFAULT_FOUNTAIN_PASSES,
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Fountain passes to write, each led by the manifest"
+    )]
+    passes: u64,
+
+    #[arg(
+        short,
+        long,
+        value_enum,
+        default_value_t,
+        help = "Compression applied before framing"
+    )]
+    compression: CompressionArg,
+
+    #[command(flatten)]
+    passphrase: PassphraseArgs,
+}
+
+pub fn run(args: EncodeArgs, matches: &ArgMatches) -> Result<(), CliError> {
+    if matches.value_source("passes") == Some(ValueSource::CommandLine)
+        && !matches!(args.framing, Framing::Fountain)
+    {
+        return Err(CliError::InvalidArgument(
+            "--passes only applies to fountain framing".to_string(),
+        ));
+    }
+    if matches.value_source("compression") == Some(ValueSource::CommandLine)
+        && matches!(args.framing, Framing::StructuredAppend)
+    {
+        return Err(CliError::InvalidArgument(
+            "compression is not supported with structured-append framing".to_string(),
+        ));
+    }
+
+    let version = Version::Normal(args.version);
+    validate_version(version)?;
+
+    let data = read_input(&args.file)?;
+    let passphrase = args.passphrase.read()?;
+    let sequence = build_sequence(&data, version, passphrase, &args)?;
+    let count = sequence.len();
+
+    prepare_output(&args.output, args.force)?;
+    match sequence {
+        Sequence::StructuredAppend(symbols) => {
+            write_images(symbols.into_iter().map(Ok), count, &args)?
+        }
+        Sequence::Frames { encoder, count } => {
+            let codes = encoder
+                .frames()
+                .take(count)
+                .map(|frame| frame.map(|frame| frame.code));
+            write_images(codes, count, &args)?
+        }
+    }
+
+    let source = if is_stdio(&args.file) {
+        Cow::Borrowed("standard input")
+    } else {
+        args.file.to_string_lossy()
+    };
+    eprintln!(
+        "Wrote {} frames for {} ({} bytes) to {}",
+        count,
+        source,
+        data.len(),
+        args.output.display()
+    );
+    Ok(())
+}
+
+// Structured Append has at most 16 symbols; a qsc sequence can run to
+// `MAX_SEQUENCE_FRAMES` frames, so those are only built as they are written.
+enum Sequence {
+    StructuredAppend(Vec<Symbol>),
+    Frames { encoder: Encoder, count: usize },
+}
+
+impl Sequence {
+    fn len(&self) -> usize {
+        match self {
+            Sequence::StructuredAppend(symbols) => symbols.len(),
+            Sequence::Frames { count, .. } => *count,
+        }
+    }
+}
+
+fn build_sequence(
+    data: &[u8],
+    version: Version,
+    passphrase: Option<String>,
+    args: &EncodeArgs,
+) -> Result<Sequence, CliError> {
+    let mode = match args.framing {
+        Framing::Chunked => TransferMode::Chunked,
+        Framing::Fountain => TransferMode::Fountain,
+        Framing::StructuredAppend => {
+            if passphrase.is_some() {
+                return Err(CliError::InvalidArgument(
+                    "encryption is not supported with structured-append framing".to_string(),
+                ));
+            }
+            return Ok(Sequence::StructuredAppend(encode_structured_append(
+                data,
+                version,
+                args.ec_level,
+            )?));
+        }
+    };
+
+    let options = EncodeOptions {
+        version,
+        ec_level: args.ec_level,
+        mode,
+        compression: args.compression.into(),
+        passphrase,
+        ..EncodeOptions::default()
+    };
+    let encoder = Encoder::new(data, options)?;
+    let passes = match mode {
+        TransferMode::Chunked => 1,
+        TransferMode::Fountain => usize::try_from(args.passes).unwrap_or(usize::MAX),
+    };
+
+    let count = (encoder.frame_count() + 1)
+        .checked_mul(passes)
+        .filter(|count| *count <= MAX_SEQUENCE_FRAMES)
+        .ok_or_else(|| {
+            CliError::InvalidArgument(format!(
+                "--passes {} would write more than {} images",
+                passes, MAX_SEQUENCE_FRAMES
+            ))
+        })?;
+    Ok(Sequence::Frames { encoder, count })
+}
+
+// Frames left by an earlier, longer run would be decoded along with the new
+// ones, so a directory that already has files is only reused with --force.
+fn prepare_output(dir: &Path, force: bool) -> Result<(), CliError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return fs::create_dir_all(dir).map_err(|e| CliError::io(dir, e));
+        }
+        Err(e) => return Err(CliError::io(dir, e)),
+    };
+
+    for entry in entries {
+        let entry = entry.map_err(|e| CliError::io(dir, e))?;
+        if !force {
+            return Err(CliError::InvalidArgument(format!(
+                "{} is not empty, pass --force to replace the frames in it",
+                dir.display()
+            )));
+        }
+        let path = entry.path();
+        let is_frame = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.starts_with(FRAME_FILE_PREFIX));
+        if is_frame && path.is_file() {
+            fs::remove_file(&path).map_err(|e| CliError::io(&path, e))?;
+        }
+    }
+    Ok(())
+}
+
+fn write_images<M: Matrix>(
+    codes: impl Iterator<Item = Result<M, qsc_core::Error>>,
+    count: usize,
+    args: &EncodeArgs,
+) -> Result<(), CliError> {
+    for (index, code) in codes.enumerate() {
+        let path = args
+            .output
+            .join(frame_file_name(index, count, args.format.extension()));
+        write_image(&path, &code?, args)?;
+    }
+    Ok(())
+}
+
+// Side of the image in pixels (PNG) or user units (SVG).
+fn image_size(code: &dyn Matrix, args: &EncodeArgs) -> Result<u32, CliError> {
+    u32::try_from(code.width())
+        .ok()
+        .and_then(|width| width.checked_add(args.quiet_zone.checked_mul(2)?))
+        .and_then(|modules| modules.checked_mul(args.module_size))
+        .ok_or_else(|| {
+            CliError::InvalidArgument(format!(
+                "a quiet zone of {} and module size of {} overflow the image size",
+                args.quiet_zone, args.module_size
+            ))
+        })
+}
+
+fn write_image(path: &Path, code: &dyn Matrix, args: &EncodeArgs) -> Result<(), CliError> {
+    let canvas_size = image_size(code, args)?;
+    let bytes = match args.format {
+        ImageFormat::Png => render_png(code, canvas_size, args.quiet_zone)?,
+        ImageFormat::Svg => render_svg(code, args.module_size, args.quiet_zone).into_bytes(),
+    };
+    fs::write(path, bytes).map_err(|e| CliError::io(path, e))
+}
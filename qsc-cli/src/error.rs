@@ -0,0 +1,52 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum CliError {
+    Core(qsc_core::Error),
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Image {
+        path: PathBuf,
+        source: image::ImageError,
+    },
+    InvalidArgument(String),
+}
+
+impl CliError {
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        CliError::Io {
+            path: path.into(),
+            source,
+        }
+    }
+
+    pub fn image(path: impl Into<PathBuf>, source: image::ImageError) -> Self {
+        CliError::Image {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Core(e) => write!(f, "{}", e),
+            CliError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            CliError::Image { path, source } => write!(f, "{}: {}", path.display(), source),
+            CliError::InvalidArgument(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+impl From<qsc_core::Error> for CliError {
+    fn from(e: qsc_core::Error) -> Self {
+        CliError::Core(e)
+    }
+}
@@ -0,0 +1,60 @@
+mod args;
+mod decode;
+mod encode;
+mod error;
+mod passphrase;
+mod play;
+
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use std::path::Path;
+use std::process::ExitCode;
+
+// Stands for standard input or output in place of a file name.
+const STDIO_PATH: &str = "-";
+
+#[derive(Parser)]
+#[command(
+    name = "qsc",
+    about = "Quick Sequence Code: files as sequences of QR codes"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(
+        about = "Write one image per QR code of the sequence to a directory, reading - as stdin"
+    )]
+    Encode(encode::EncodeArgs),
+    #[command(about = "Scan frame images and write the file they carry, to stdout by default")]
+    Decode(decode::DecodeArgs),
+    #[command(about = "Play the sequence as QR codes in the terminal, reading - as stdin")]
+    Play(play::PlayArgs),
+}
+
+fn main() -> ExitCode {
+    // The raw matches tell explicit flags apart from their defaults.
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let (_, command_matches) = matches.subcommand().expect("a subcommand is required");
+
+    let result = match cli.command {
+        Command::Encode(args) => encode::run(args, command_matches),
+        Command::Decode(args) => decode::run(args),
+        Command::Play(args) => play::run(args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn is_stdio(path: &Path) -> bool {
+    path == Path::new(STDIO_PATH)
+}
@@ -0,0 +1,38 @@
+use crate::error::CliError;
+use clap::Args;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+// Kept off the command line, where `ps` and shell history would expose it.
+pub const PASSPHRASE_ENV: &str = "QSC_PASSPHRASE";
+
+#[derive(Debug, Args)]
+pub struct PassphraseArgs {
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Read the passphrase from the first line of this file instead of $QSC_PASSPHRASE"
+    )]
+    passphrase_file: Option<PathBuf>,
+}
+
+impl PassphraseArgs {
+    pub fn read(&self) -> Result<Option<String>, CliError> {
+        let Some(path) = &self.passphrase_file else {
+            return Ok(env::var(PASSPHRASE_ENV)
+                .ok()
+                .filter(|passphrase| !passphrase.is_empty()));
+        };
+
+        let contents = fs::read_to_string(path).map_err(|e| CliError::io(path, e))?;
+        let passphrase = contents.lines().next().unwrap_or_default();
+        if passphrase.is_empty() {
+            return Err(CliError::InvalidArgument(format!(
+                "{}: passphrase file is empty",
+                path.display()
+            )));
+        }
+        Ok(Some(passphrase.to_string()))
+    }
+}
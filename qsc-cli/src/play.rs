@@ -0,0 +1,133 @@
+use crate::args::{CompressionArg, Framing, parse_ec_level_arg, read_input};
+use crate::error::CliError;
+use crate::passphrase::PassphraseArgs;
+use clap::Args;
+use qsc_core::qrcode::{EcLevel, Version};
+use qsc_core::{
+    DEFAULT_QR_CODE_VERSION, DEFAULT_QUIET_ZONE, EncodeOptions, Encoder, TerminalOptions,
+    TransferMode, encode_structured_append, frame_caption, play_encoder, play_terminal,
+    validate_version,
+};
+use std::io;
+use std::path::PathBuf;
+
+const DEFAULT_FPS: u32 = 5;
+const MAX_FPS: u32 = 60;
+
+#[derive(Debug, Args)]
+pub struct PlayArgs {
+    #[arg(help = "File to play, or - for standard input")]
+    file: PathBuf,
+
+    #[arg(long, default_value_t = DEFAULT_QR_CODE_VERSION, help = "QR version, 1 to 40")]
+    version: i16,
+
+    #[arg(short, long, default_value = "L", value_parser = parse_ec_level_arg, help = "Error correction level: L, M, Q or H")]
+    ec_level: EcLevel,
+
+    #[arg(short, long, default_value_t = DEFAULT_QUIET_ZONE, help = "Light border in modules")]
+    quiet_zone: u32,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t,
+        help = "How the file is split over QR codes"
+    )]
+    framing: Framing,
+
+    #[arg(
+        short,
+        long,
+        value_enum,
+        default_value_t,
+        help = "Compression applied before framing"
+    )]
+    compression: CompressionArg,
+
+    #[arg(
+        long,
+        default_value_t = DEFAULT_FPS,
+        value_parser = clap::value_parser!(u32).range(1..=MAX_FPS as i64),
+        help = "Frames shown per second"
+    )]
+    fps: u32,
+
+    #[arg(
+        long,
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Times to play the sequence; forever when omitted"
+    )]
+    loops: Option<u64>,
+
+    #[arg(
+        long,
+        help = "Paint black and white cells instead of using the terminal's colours"
+    )]
+    colors: bool,
+
+    #[arg(
+        long,
+        help = "The terminal draws dark text on a light background; ignored with --colors"
+    )]
+    light_background: bool,
+
+    #[command(flatten)]
+    passphrase: PassphraseArgs,
+}
+
+pub fn run(args: PlayArgs) -> Result<(), CliError> {
+    let version = Version::Normal(args.version);
+    validate_version(version)?;
+
+    let data = read_input(&args.file)?;
+    let passphrase = args.passphrase.read()?;
+    let loops = args
+        .loops
+        .map(|loops| usize::try_from(loops).unwrap_or(usize::MAX));
+    let options = TerminalOptions {
+        quiet_zone: args.quiet_zone,
+        colors: args.colors,
+        dark_background: !args.light_background,
+    };
+    let mut out = io::stdout().lock();
+    let write_error = |e| CliError::io("standard output", e);
+
+    let mode = match args.framing {
+        Framing::Chunked => TransferMode::Chunked,
+        Framing::Fountain => TransferMode::Fountain,
+        Framing::StructuredAppend => {
+            if passphrase.is_some() {
+                return Err(CliError::InvalidArgument(
+                    "encryption is not supported with structured-append framing".to_string(),
+                ));
+            }
+            let symbols = encode_structured_append(&data, version, args.ec_level)?;
+            let total = symbols.len();
+            let limit = loops.map_or(usize::MAX, |loops| loops.saturating_mul(total));
+            let frames = symbols
+                .iter()
+                .enumerate()
+                .cycle()
+                .take(limit)
+                .map(|(index, symbol)| {
+                    let caption = frame_caption(index, total, symbol.data());
+                    (symbol.clone(), caption)
+                });
+            return play_terminal(&mut out, frames, &options, args.fps).map_err(write_error);
+        }
+    };
+
+    let encoder = Encoder::new(
+        &data[..],
+        EncodeOptions {
+            version,
+            ec_level: args.ec_level,
+            mode,
+            compression: args.compression.into(),
+            passphrase,
+            ..EncodeOptions::default()
+        },
+    )?;
+    play_encoder(&mut out, &encoder, &options, args.fps, loops).map_err(write_error)
+}
@@ -0,0 +1,229 @@
+use image::codecs::gif::GifEncoder;
+use image::{Frame, RgbaImage};
+use qsc_core::qrcode::Version;
+use qsc_core::{
+    AnimationFormat, AnimationOptions, DEFAULT_QUIET_ZONE, EncodeOptions, rasterize_luma,
+    render_animation,
+};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("qsc-cli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn qsc(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_qsc"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn encode(input: &Path, output: &Path, extra: &[&str]) {
+    let mut args = vec![
+        "encode",
+        input.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+        "--version",
+        "6",
+        "-m",
+        "3",
+    ];
+    args.extend_from_slice(extra);
+    let result = qsc(&args);
+    assert!(result.status.success(), "{:?}", result);
+}
+
+#[test]
+fn decode_round_trips_a_directory_of_frames() {
+    let dir = scratch_dir("decode-dir");
+    let input = dir.join("input.bin");
+    let data: Vec<u8> = (0..1500u32).map(|i| (i * 37 % 253) as u8).collect();
+    fs::write(&input, &data).unwrap();
+    let frames = dir.join("frames");
+    let passphrase_file = dir.join("passphrase.txt");
+    fs::write(&passphrase_file, "secret\n").unwrap();
+    encode(
+        &input,
+        &frames,
+        &[
+            "-c",
+            "none",
+            "--passphrase-file",
+            passphrase_file.to_str().unwrap(),
+        ],
+    );
+
+    // One frame re-saved as JPEG, the way a camera or screenshot tool would.
+    let first = fs::read_dir(&frames)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .min()
+        .unwrap();
+    image::open(&first)
+        .unwrap()
+        .to_luma8()
+        .save(first.with_extension("JPG"))
+        .unwrap();
+    fs::remove_file(&first).unwrap();
+
+    let output = dir.join("output.bin");
+    let decode = |passphrase: &str| {
+        Command::new(env!("CARGO_BIN_EXE_qsc"))
+            .args(["decode", frames.to_str().unwrap(), "-o"])
+            .arg(&output)
+            .env("QSC_PASSPHRASE", passphrase)
+            .output()
+            .unwrap()
+    };
+    assert!(!decode("wrong").status.success());
+    let result = decode("secret");
+    assert!(result.status.success(), "{:?}", result);
+    assert_eq!(fs::read(&output).unwrap(), data);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn decode_joins_structured_append_and_reports_missing_frames() {
+    let dir = scratch_dir("decode-missing");
+    let input = dir.join("input.txt");
+    let text = "structured append survives the round trip ".repeat(10);
+    fs::write(&input, &text).unwrap();
+
+    let symbols = dir.join("symbols");
+    encode(&input, &symbols, &["--framing", "structured-append"]);
+    let output = dir.join("output.txt");
+    let result = qsc(&[
+        "decode",
+        symbols.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+    ]);
+    assert!(result.status.success(), "{:?}", result);
+    assert_eq!(fs::read_to_string(&output).unwrap(), text);
+
+    let frames = dir.join("frames");
+    encode(&input, &frames, &["-c", "none"]);
+    let mut paths: Vec<PathBuf> = fs::read_dir(&frames)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+    fs::remove_file(&paths[2]).unwrap();
+
+    let result = qsc(&[
+        "decode",
+        frames.to_str().unwrap(),
+        "-o",
+        dir.join("partial.txt").to_str().unwrap(),
+    ]);
+    assert!(!result.status.success());
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(stderr.contains("missing blocks: 1"), "{}", stderr);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn decode_reads_animated_gif_and_apng() {
+    let dir = scratch_dir("decode-animation");
+    let data: Vec<u8> = (0..900u32).map(|i| (i * 13 % 241) as u8).collect();
+    let options = EncodeOptions {
+        version: Version::Normal(5),
+        compression: qsc_core::Compression::None,
+        ..EncodeOptions::default()
+    };
+    let codes: Vec<_> = qsc_core::encode(&data, &options)
+        .unwrap()
+        .into_iter()
+        .map(|frame| frame.code)
+        .collect();
+
+    // Found by its extension when a directory is decoded.
+    let apng = dir.join("animations");
+    fs::create_dir_all(&apng).unwrap();
+    let animation = AnimationOptions {
+        format: AnimationFormat::Apng,
+        ..AnimationOptions::default()
+    };
+    fs::write(
+        apng.join("sequence.apng"),
+        render_animation(&codes, &animation).unwrap(),
+    )
+    .unwrap();
+
+    // A screen recording, with a blank frame where the code was not on screen.
+    let size = animation.canvas_size(codes[0].width()).unwrap();
+    let mut frames: Vec<Frame> = codes
+        .iter()
+        .map(|code| {
+            let rgba = rasterize_luma(code, size, DEFAULT_QUIET_ZONE)
+                .into_iter()
+                .flat_map(|luma| [luma, luma, luma, 255])
+                .collect();
+            Frame::new(RgbaImage::from_raw(size, size, rgba).unwrap())
+        })
+        .collect();
+    frames.insert(
+        1,
+        Frame::new(RgbaImage::from_pixel(size, size, [255; 4].into())),
+    );
+    let gif = dir.join("recording.gif");
+    let mut bytes = Vec::new();
+    GifEncoder::new(&mut bytes).encode_frames(frames).unwrap();
+    fs::write(&gif, bytes).unwrap();
+
+    let last = codes.len();
+    for (input, found) in [
+        (&apng, format!("codes in frames 0-{}", last - 1)),
+        (&gif, format!("codes in frames 0, 2-{}", last)),
+    ] {
+        let output = dir.join("output.bin");
+        let result = qsc(&[
+            "decode",
+            input.to_str().unwrap(),
+            "-o",
+            output.to_str().unwrap(),
+        ]);
+        assert!(result.status.success(), "{:?}", result);
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        assert!(stderr.contains(&found), "{}", stderr);
+        assert_eq!(fs::read(&output).unwrap(), data);
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn encode_reads_stdin_and_decode_writes_stdout() {
+    let dir = scratch_dir("decode-pipe");
+    let frames = dir.join("frames");
+    let data: Vec<u8> = (0..700u32).map(|i| (i * 7 % 256) as u8).collect();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_qsc"))
+        .args(["encode", "-", "-o", frames.to_str().unwrap(), "-m", "3"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(&data).unwrap();
+    let result = child.wait_with_output().unwrap();
+    assert!(result.status.success(), "{:?}", result);
+    assert!(result.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("standard input"));
+
+    let result = qsc(&["decode", frames.to_str().unwrap()]);
+    assert!(result.status.success(), "{:?}", result);
+    assert_eq!(result.stdout, data);
+    assert!(String::from_utf8_lossy(&result.stderr).contains("codes"));
+
+    fs::remove_dir_all(dir).unwrap();
+}
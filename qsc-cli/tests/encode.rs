@@ -0,0 +1,199 @@
+use qsc_core::qrcode::{EcLevel, Version};
+use qsc_core::{EncodeOptions, Encoder, frame_file_name};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("qsc-cli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn qsc(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_qsc"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn encode_writes_one_png_per_frame() {
+    let dir = scratch_dir("png");
+    let input = dir.join("input.bin");
+    let data: Vec<u8> = (0..2000u32).map(|i| (i * 31 % 251) as u8).collect();
+    fs::write(&input, &data).unwrap();
+    let output = dir.join("frames");
+
+    let result = qsc(&[
+        "encode",
+        input.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+        "--version",
+        "5",
+        "-e",
+        "m",
+        "-m",
+        "3",
+        "-c",
+        "none",
+    ]);
+    assert!(result.status.success(), "{:?}", result);
+
+    let options = EncodeOptions {
+        version: Version::Normal(5),
+        ec_level: EcLevel::M,
+        compression: qsc_core::Compression::None,
+        ..EncodeOptions::default()
+    };
+    let total = Encoder::new(data, options).unwrap().frame_count() + 1;
+    assert_eq!(fs::read_dir(&output).unwrap().count(), total);
+
+    // Version 5 is 37 modules wide plus a 4 module quiet zone on each side.
+    let png = fs::read(output.join(frame_file_name(0, total, "png"))).unwrap();
+    assert_eq!(&png[1..4], b"PNG");
+    assert_eq!(u32::from_be_bytes(png[16..20].try_into().unwrap()), 45 * 3);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn encode_writes_svg_and_rejects_bad_options() {
+    let dir = scratch_dir("svg");
+    let input = dir.join("input.txt");
+    fs::write(&input, "hello from the command line").unwrap();
+    let output = dir.join("frames");
+
+    let result = qsc(&[
+        "encode",
+        input.to_str().unwrap(),
+        "-o",
+        output.to_str().unwrap(),
+        "-f",
+        "svg",
+        "--framing",
+        "structured-append",
+    ]);
+    assert!(result.status.success(), "{:?}", result);
+    let svg = fs::read_to_string(output.join(frame_file_name(0, 1, "svg"))).unwrap();
+    assert!(svg.contains("<svg") && svg.trim_end().ends_with("</svg>"));
+
+    let result = qsc(&[
+        "encode",
+        input.to_str().unwrap(),
+        "-o",
+        dir.join("compressed").to_str().unwrap(),
+        "--framing",
+        "structured-append",
+        "-c",
+        "deflate",
+    ]);
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("compression"));
+
+    let result = qsc(&["encode", input.to_str().unwrap(), "--version", "41"]);
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("41"));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn passes_only_apply_to_fountain_framing_and_sizes_are_checked() {
+    let dir = scratch_dir("passes");
+    let input = dir.join("input.bin");
+    fs::write(&input, vec![7u8; 600]).unwrap();
+    let output = dir.join("frames");
+    let args = |extra: &[&str]| {
+        let mut args = vec![
+            "encode",
+            input.to_str().unwrap(),
+            "-o",
+            output.to_str().unwrap(),
+            "-m",
+            "1",
+            "-c",
+            "none",
+        ];
+        args.extend_from_slice(extra);
+        qsc(&args)
+    };
+
+    let result = args(&["--passes", "3"]);
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("--passes"));
+
+    for extra in [
+        &["--framing", "fountain", "--passes", "18446744073709551615"][..],
+        &["-m", "4294967295"][..],
+        &["-f", "svg", "-q", "4294967295"][..],
+    ] {
+        let result = args(extra);
+        assert!(!result.status.success(), "{:?}", extra);
+        assert!(String::from_utf8_lossy(&result.stderr).contains("error:"));
+    }
+
+    let result = args(&["--framing", "fountain", "--passes", "3"]);
+    assert!(result.status.success(), "{:?}", result);
+    let options = EncodeOptions {
+        mode: qsc_core::TransferMode::Fountain,
+        compression: qsc_core::Compression::None,
+        ..EncodeOptions::default()
+    };
+    let total = Encoder::new(vec![7u8; 600], options).unwrap().frame_count() + 1;
+    assert_eq!(fs::read_dir(&output).unwrap().count(), 3 * total);
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn encode_refuses_a_used_output_directory_without_force() {
+    let dir = scratch_dir("force");
+    let input = dir.join("input.bin");
+    let output = dir.join("frames");
+    let encode = |len: usize, extra: &[&str]| {
+        fs::write(&input, vec![3u8; len]).unwrap();
+        let mut args = vec![
+            "encode",
+            input.to_str().unwrap(),
+            "-o",
+            output.to_str().unwrap(),
+            "--version",
+            "5",
+            "-m",
+            "1",
+            "-c",
+            "none",
+        ];
+        args.extend_from_slice(extra);
+        qsc(&args)
+    };
+    let frames = || fs::read_dir(&output).unwrap().count();
+
+    assert!(encode(2000, &[]).status.success());
+    let long_run = frames();
+    fs::write(output.join("notes.txt"), "kept").unwrap();
+
+    let result = encode(50, &[]);
+    assert!(!result.status.success());
+    assert!(String::from_utf8_lossy(&result.stderr).contains("--force"));
+    assert_eq!(frames(), long_run + 1);
+
+    let result = encode(50, &["--force"]);
+    assert!(result.status.success(), "{:?}", result);
+    let names: Vec<String> = fs::read_dir(&output)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    assert!(names.len() < long_run);
+    assert!(names.contains(&"notes.txt".to_string()));
+    assert!(
+        names
+            .iter()
+            .all(|name| name == "notes.txt" || name.ends_with("-of-0002.png"))
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
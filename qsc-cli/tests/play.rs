@@ -0,0 +1,63 @@
+use qsc_core::qrcode::Version;
+use qsc_core::{Compression, EncodeOptions, Encoder};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("qsc-cli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn qsc(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_qsc"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn play_draws_each_frame_for_the_requested_loops() {
+    let dir = scratch_dir("play");
+    let input = dir.join("input.bin");
+    let data: Vec<u8> = (0..600u32).map(|i| (i * 29 % 251) as u8).collect();
+    fs::write(&input, &data).unwrap();
+    let options = EncodeOptions {
+        version: Version::Normal(5),
+        compression: Compression::None,
+        ..EncodeOptions::default()
+    };
+    let total = Encoder::new(&data[..], options).unwrap().frame_count() + 1;
+    let input = input.to_str().unwrap();
+
+    let play = |extra: &[&str]| {
+        let mut args = vec!["play", input, "--version", "5", "-c", "none", "--fps", "60"];
+        args.extend_from_slice(extra);
+        let result = qsc(&args);
+        assert!(result.status.success(), "{:?}", result);
+        String::from_utf8(result.stdout).unwrap()
+    };
+
+    let stdout = play(&["--loops", "2"]);
+    assert!(stdout.contains('▀') && stdout.contains('█'));
+    assert!(!stdout.contains("\x1b[97;"));
+    assert_eq!(stdout.matches(&format!("1 / {} ·", total)).count(), 2);
+    assert_eq!(
+        stdout.matches(&format!("{} / {} ·", total, total)).count(),
+        2
+    );
+
+    let stdout = play(&["--loops", "1", "--colors"]);
+    assert!(stdout.contains("\x1b[30;40m▀") && stdout.contains("\x1b[97;107m▀"));
+    assert_eq!(stdout.matches(" / ").count(), total);
+
+    let stdout = play(&["--loops", "1", "--framing", "structured-append"]);
+    assert!(stdout.contains('▀') && stdout.contains("1 / "));
+
+    assert!(!qsc(&["play", input, "--fps", "0"]).status.success());
+    assert!(!qsc(&["play", input, "--loops", "0"]).status.success());
+
+    fs::remove_dir_all(dir).unwrap();
+}
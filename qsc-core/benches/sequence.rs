@@ -0,0 +1,143 @@
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use qsc_core::qrcode::{EcLevel, QrCode, Version};
+use qsc_core::{
+    Compression, DEFAULT_QR_CODE_VERSION, DEFAULT_QUIET_ZONE, EncodeOptions, Encoder,
+    FRAME_HEADER_SIZE, check_canvas_size, encode_byte_mode, get_max_qr_capacity, rasterize_rgba,
+};
+use std::hint::black_box;
+
+// The browser's default canvas, `DEFAULT_QR_CODE_SIZE` in qsc-generator.
+const CANVAS_SIZE: u32 = 200;
+
+const VERSIONS: [i16; 5] = [5, 8, 15, 25, 40];
+const FILE_SIZES: [usize; 3] = [16 * 1024, 256 * 1024, 1024 * 1024];
+
+// Deterministic and incompressible enough that Deflate would not help.
+fn file(len: usize) -> Vec<u8> {
+    let mut state = 0x2545_f491u32;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect()
+}
+
+fn options(version: i16) -> EncodeOptions {
+    EncodeOptions {
+        version: Version::Normal(version),
+        compression: Compression::None,
+        ..EncodeOptions::default()
+    }
+}
+
+fn capacity(c: &mut Criterion) {
+    let mut group = c.benchmark_group("capacity");
+    group.bench_function("get_max_qr_capacity", |b| {
+        b.iter(|| {
+            (1..=40)
+                .flat_map(|v| [EcLevel::L, EcLevel::M, EcLevel::Q, EcLevel::H].map(|ec| (v, ec)))
+                .map(|(v, ec)| get_max_qr_capacity(black_box(Version::Normal(v)), ec))
+                .sum::<usize>()
+        })
+    });
+
+    // Chunking a file: manifest digest plus frame count, no QR codes yet.
+    for len in FILE_SIZES {
+        let data = file(len);
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::new("encoder_new", len), &data, |b, data| {
+            b.iter(|| Encoder::new(black_box(&data[..]), options(DEFAULT_QR_CODE_VERSION)))
+        });
+    }
+    group.finish();
+}
+
+fn qr_code(c: &mut Criterion) {
+    let mut group = c.benchmark_group("qr_code");
+    for version in VERSIONS {
+        let len = get_max_qr_capacity(Version::Normal(version), EcLevel::L);
+        let payload = file(len);
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(
+            BenchmarkId::new("with_version", version),
+            &payload,
+            |b, payload| {
+                b.iter(|| {
+                    QrCode::with_version(black_box(payload), Version::Normal(version), EcLevel::L)
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("encode_byte_mode", version),
+            &payload,
+            |b, payload| {
+                b.iter(|| {
+                    encode_byte_mode(black_box(payload), Version::Normal(version), EcLevel::L)
+                })
+            },
+        );
+
+        // A full data frame: header, payload slice and QR code.
+        let encoder = Encoder::new(file(len - FRAME_HEADER_SIZE), options(version)).unwrap();
+        group.bench_with_input(
+            BenchmarkId::new("frame", version),
+            &encoder,
+            |b, encoder| b.iter(|| encoder.frame(black_box(0))),
+        );
+    }
+    group.finish();
+}
+
+fn rasterize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rasterize");
+    for version in VERSIONS {
+        let code = QrCode::with_version(b"qsc", Version::Normal(version), EcLevel::L).unwrap();
+        let scaled = (code.width() as u32 + 2 * DEFAULT_QUIET_ZONE) * 4;
+        // The default canvas is too small for the larger versions.
+        let sizes = [CANVAS_SIZE, scaled].into_iter().filter(|canvas_size| {
+            check_canvas_size(Version::Normal(version), *canvas_size, DEFAULT_QUIET_ZONE).is_ok()
+        });
+        for canvas_size in sizes {
+            let pixels = canvas_size as u64 * canvas_size as u64;
+            group.throughput(Throughput::Elements(pixels));
+            group.bench_with_input(
+                BenchmarkId::new(format!("v{}", version), format!("{}px", canvas_size)),
+                &canvas_size,
+                |b, canvas_size| {
+                    b.iter(|| rasterize_rgba(&code, black_box(*canvas_size), DEFAULT_QUIET_ZONE))
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+// Everything the browser does per file before touching the DOM: chunking,
+// one QR code per frame and one canvas-sized raster per frame.
+fn sequence(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sequence");
+    group.sample_size(10);
+    for len in FILE_SIZES {
+        let data = file(len);
+        group.throughput(Throughput::Bytes(len as u64));
+        group.bench_with_input(BenchmarkId::new("canvas", len), &data, |b, data| {
+            b.iter(|| {
+                let encoder = Encoder::new(&data[..], options(DEFAULT_QR_CODE_VERSION)).unwrap();
+                encoder
+                    .frames()
+                    .take(encoder.frame_count() + 1)
+                    .map(|frame| {
+                        rasterize_rgba(&frame.unwrap().code, CANVAS_SIZE, DEFAULT_QUIET_ZONE).len()
+                    })
+                    .sum::<usize>()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, capacity, qr_code, rasterize, sequence);
+criterion_main!(benches);
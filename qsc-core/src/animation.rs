@@ -0,0 +1,160 @@
+use crate::constants::{
+    DEFAULT_ANIMATION_SCALE, DEFAULT_FRAME_DELAY_MS, DEFAULT_QUIET_ZONE, MAX_ANIMATION_SIZE,
+};
+use crate::error::Error;
+use crate::matrix::Matrix;
+use crate::render::rasterize_luma;
+use std::borrow::Cow;
+
+// Black at index 0, white at index 1, matching the luma raster once halved.
+const GIF_PALETTE: [u8; 6] = [0, 0, 0, 255, 255, 255];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnimationFormat {
+    #[default]
+    Gif,
+    Apng,
+}
+
+impl AnimationFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            AnimationFormat::Gif => "gif",
+            AnimationFormat::Apng => "png",
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            AnimationFormat::Gif => "image/gif",
+            AnimationFormat::Apng => "image/apng",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnimationOptions {
+    pub format: AnimationFormat,
+    pub frame_delay_ms: u32,
+    // Pixels per module.
+    pub scale: u32,
+    pub quiet_zone: u32,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        Self {
+            format: AnimationFormat::default(),
+            frame_delay_ms: DEFAULT_FRAME_DELAY_MS,
+            scale: DEFAULT_ANIMATION_SCALE,
+            quiet_zone: DEFAULT_QUIET_ZONE,
+        }
+    }
+}
+
+impl AnimationOptions {
+    // Side of the shared canvas in pixels, for GIF and APNG alike.
+    pub fn canvas_size(&self, width: usize) -> Result<u32, Error> {
+        u32::try_from(width)
+            .ok()
+            .and_then(|width| width.checked_add(self.quiet_zone.checked_mul(2)?))
+            .and_then(|modules| modules.checked_mul(self.scale.max(1)))
+            .filter(|size| *size <= MAX_ANIMATION_SIZE)
+            .ok_or_else(|| {
+                Error::ImageEncoding(format!(
+                    "animation frames would be larger than {} px",
+                    MAX_ANIMATION_SIZE
+                ))
+            })
+    }
+}
+
+// Loops forever; every frame shares one canvas sized for the widest symbol.
+pub fn render_animation<M: Matrix>(
+    matrices: &[M],
+    options: &AnimationOptions,
+) -> Result<Vec<u8>, Error> {
+    let width = matrices
+        .iter()
+        .map(|matrix| matrix.width())
+        .max()
+        .ok_or(Error::EmptySequence)?;
+    let size = options.canvas_size(width)?;
+    let frames = matrices
+        .iter()
+        .map(|matrix| rasterize_luma(matrix, size, options.quiet_zone));
+
+    match options.format {
+        AnimationFormat::Gif => encode_gif(frames, size, options.frame_delay_ms),
+        AnimationFormat::Apng => encode_apng(frames, matrices.len(), size, options.frame_delay_ms),
+    }
+}
+
+fn encode_gif(
+    frames: impl Iterator<Item = Vec<u8>>,
+    size: u32,
+    frame_delay_ms: u32,
+) -> Result<Vec<u8>, Error> {
+    let side = u16::try_from(size)
+        .map_err(|_| Error::ImageEncoding(format!("{} px is too large for GIF", size)))?;
+    let delay = u16::try_from(frame_delay_ms.div_ceil(10)).unwrap_or(u16::MAX);
+
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = gif::Encoder::new(&mut bytes, side, side, &GIF_PALETTE)
+            .map_err(|e| Error::ImageEncoding(e.to_string()))?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(|e| Error::ImageEncoding(e.to_string()))?;
+
+        for luma in frames {
+            let indices: Vec<u8> = luma.into_iter().map(|value| value >> 7).collect();
+            let frame = gif::Frame {
+                width: side,
+                height: side,
+                delay,
+                buffer: Cow::Owned(indices),
+                ..gif::Frame::default()
+            };
+            encoder
+                .write_frame(&frame)
+                .map_err(|e| Error::ImageEncoding(e.to_string()))?;
+        }
+    }
+
+    Ok(bytes)
+}
+
+fn encode_apng(
+    frames: impl Iterator<Item = Vec<u8>>,
+    frame_count: usize,
+    size: u32,
+    frame_delay_ms: u32,
+) -> Result<Vec<u8>, Error> {
+    let delay = u16::try_from(frame_delay_ms).unwrap_or(u16::MAX);
+
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, size, size);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .set_animated(frame_count as u32, 0)
+        .map_err(|e| Error::ImageEncoding(e.to_string()))?;
+    encoder
+        .set_frame_delay(delay, 1000)
+        .map_err(|e| Error::ImageEncoding(e.to_string()))?;
+
+    let mut writer = encoder
+        .write_header()
+        .map_err(|e| Error::ImageEncoding(e.to_string()))?;
+    for luma in frames {
+        writer
+            .write_image_data(&luma)
+            .map_err(|e| Error::ImageEncoding(e.to_string()))?;
+    }
+    writer
+        .finish()
+        .map_err(|e| Error::ImageEncoding(e.to_string()))?;
+
+    Ok(bytes)
+}
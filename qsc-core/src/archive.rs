@@ -0,0 +1,46 @@
+use crate::error::Error;
+use crate::matrix::Matrix;
+use crate::png::render_png;
+use crate::render::frame_file_name;
+use std::io::{Cursor, Write};
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+pub fn build_zip(files: impl IntoIterator<Item = (String, Vec<u8>)>) -> Result<Vec<u8>, Error> {
+    // PNGs are already deflated, storing them avoids a second pass for nothing.
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+
+    for (name, bytes) in files {
+        writer
+            .start_file(name, options)
+            .map_err(|e| Error::Archive(e.to_string()))?;
+        writer
+            .write_all(&bytes)
+            .map_err(|e| Error::Archive(e.to_string()))?;
+    }
+
+    let cursor = writer.finish().map_err(|e| Error::Archive(e.to_string()))?;
+    Ok(cursor.into_inner())
+}
+
+// One `frame-0001-of-0042.png` entry per code, in sequence order.
+pub fn build_png_zip<M: Matrix>(
+    matrices: &[M],
+    canvas_size: u32,
+    quiet_zone: u32,
+) -> Result<Vec<u8>, Error> {
+    let total = matrices.len();
+    let files = matrices
+        .iter()
+        .enumerate()
+        .map(|(index, matrix)| {
+            Ok((
+                frame_file_name(index, total, "png"),
+                render_png(matrix, canvas_size, quiet_zone)?,
+            ))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    build_zip(files)
+}
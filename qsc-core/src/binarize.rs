@@ -0,0 +1,135 @@
+// Splits a greyscale image into dark and light pixels with a threshold that
+// follows local lighting, in 8 px blocks averaged over their 5x5 neighbourhood.
+const BLOCK_SIZE: usize = 8;
+const MIN_DYNAMIC_RANGE: u32 = 24;
+const MIN_LOCAL_SIZE: usize = BLOCK_SIZE * 5;
+
+pub(crate) struct BitImage {
+    width: usize,
+    height: usize,
+    dark: Vec<bool>,
+}
+
+impl BitImage {
+    pub(crate) fn width(&self) -> usize {
+        self.width
+    }
+
+    pub(crate) fn height(&self) -> usize {
+        self.height
+    }
+
+    pub(crate) fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.dark[y * self.width + x]
+    }
+}
+
+pub(crate) fn binarize(pixels: &[u8], width: usize, height: usize) -> BitImage {
+    let dark = if width < MIN_LOCAL_SIZE || height < MIN_LOCAL_SIZE {
+        let threshold = otsu_threshold(pixels);
+        pixels.iter().map(|pixel| *pixel <= threshold).collect()
+    } else {
+        local_threshold(pixels, width, height)
+    };
+
+    BitImage {
+        width,
+        height,
+        dark,
+    }
+}
+
+fn otsu_threshold(pixels: &[u8]) -> u8 {
+    let mut histogram = [0u64; 256];
+    for pixel in pixels {
+        histogram[*pixel as usize] += 1;
+    }
+
+    let total = pixels.len() as f64;
+    let sum: f64 = histogram
+        .iter()
+        .enumerate()
+        .map(|(value, count)| value as f64 * *count as f64)
+        .sum();
+
+    let (mut background, mut background_sum) = (0.0, 0.0);
+    let (mut best, mut best_variance) = (127, 0.0);
+    for (value, count) in histogram.iter().enumerate() {
+        background += *count as f64;
+        background_sum += value as f64 * *count as f64;
+        let foreground = total - background;
+        if background == 0.0 || foreground == 0.0 {
+            continue;
+        }
+
+        let difference = background_sum / background - (sum - background_sum) / foreground;
+        let variance = background * foreground * difference * difference;
+        if variance > best_variance {
+            best_variance = variance;
+            best = value as u8;
+        }
+    }
+    best
+}
+
+fn local_threshold(pixels: &[u8], width: usize, height: usize) -> Vec<bool> {
+    let blocks_x = width.div_ceil(BLOCK_SIZE);
+    let blocks_y = height.div_ceil(BLOCK_SIZE);
+    let block_origin = |block: usize, size: usize| (block * BLOCK_SIZE).min(size - BLOCK_SIZE);
+
+    // Flat blocks have no edge to split on: treat them as background unless
+    // their neighbours show they sit inside a dark area.
+    let mut black_points = vec![0u32; blocks_x * blocks_y];
+    for by in 0..blocks_y {
+        let top = block_origin(by, height);
+        for bx in 0..blocks_x {
+            let left = block_origin(bx, width);
+            let (mut sum, mut min, mut max) = (0u32, u32::MAX, 0u32);
+            for y in top..top + BLOCK_SIZE {
+                for pixel in &pixels[y * width + left..y * width + left + BLOCK_SIZE] {
+                    let pixel = *pixel as u32;
+                    sum += pixel;
+                    min = min.min(pixel);
+                    max = max.max(pixel);
+                }
+            }
+
+            let mut average = sum / (BLOCK_SIZE * BLOCK_SIZE) as u32;
+            if max - min <= MIN_DYNAMIC_RANGE {
+                average = min / 2;
+                if by > 0 && bx > 0 {
+                    let neighbours = (black_points[(by - 1) * blocks_x + bx]
+                        + 2 * black_points[by * blocks_x + bx - 1]
+                        + black_points[(by - 1) * blocks_x + bx - 1])
+                        / 4;
+                    if min < neighbours {
+                        average = neighbours;
+                    }
+                }
+            }
+            black_points[by * blocks_x + bx] = average;
+        }
+    }
+
+    let mut dark = vec![false; width * height];
+    for by in 0..blocks_y {
+        let top = block_origin(by, height);
+        let cy = by.clamp(2, blocks_y - 3);
+        for bx in 0..blocks_x {
+            let left = block_origin(bx, width);
+            let cx = bx.clamp(2, blocks_x - 3);
+            let sum: u32 = (cy - 2..=cy + 2)
+                .flat_map(|y| (cx - 2..=cx + 2).map(move |x| (x, y)))
+                .map(|(x, y)| black_points[y * blocks_x + x])
+                .sum();
+            let threshold = sum / 25;
+
+            for y in top..top + BLOCK_SIZE {
+                for x in left..left + BLOCK_SIZE {
+                    dark[y * width + x] = pixels[y * width + x] as u32 <= threshold;
+                }
+            }
+        }
+    }
+    dark
+}
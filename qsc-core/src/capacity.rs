@@ -0,0 +1,32 @@
+use crate::error::Error;
+use qrcode::bits::Bits;
+use qrcode::types::{Mode, QrError};
+use qrcode::{EcLevel, QrCode, Version};
+
+pub(crate) const MODE_INDICATOR_BITS: usize = 4;
+
+pub(crate) fn data_capacity_bits(version: Version, ec_level: EcLevel) -> Result<usize, Error> {
+    if let Version::Micro(_) = version {
+        return Err(Error::Qr(QrError::InvalidVersion));
+    }
+    Ok(Bits::new(version).max_len(ec_level)?)
+}
+
+// Byte-mode capacity straight from the symbol's data codeword count: one mode
+// indicator and one character count, the rest is payload.
+pub fn get_max_qr_capacity(version: Version, ec_level: EcLevel) -> usize {
+    data_capacity_bits(version, ec_level)
+        .map(|bits| {
+            bits.saturating_sub(MODE_INDICATOR_BITS + Mode::Byte.length_bits_count(version)) / 8
+        })
+        .unwrap_or(0)
+}
+
+// Always a single byte-mode segment, so `get_max_qr_capacity` bytes are
+// guaranteed to fit whatever the payload looks like.
+pub fn encode_byte_mode(data: &[u8], version: Version, ec_level: EcLevel) -> Result<QrCode, Error> {
+    let mut bits = Bits::new(version);
+    bits.push_byte_data(data)?;
+    bits.push_terminator(ec_level)?;
+    Ok(QrCode::with_bits(bits, ec_level)?)
+}
@@ -0,0 +1,49 @@
+use crate::constants::FLAG_DEFLATE;
+use crate::error::Error;
+use miniz_oxide::deflate::compress_to_vec;
+use miniz_oxide::inflate::decompress_to_vec_with_limit;
+
+const DEFLATE_LEVEL: u8 = 9;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Deflate,
+    #[default]
+    Auto,
+}
+
+impl Compression {
+    pub fn flags(self) -> u8 {
+        match self {
+            Compression::Deflate => FLAG_DEFLATE,
+            Compression::None | Compression::Auto => 0,
+        }
+    }
+}
+
+// Returns the bytes to chunk and the codec actually applied; `Auto` only keeps
+// the deflated stream when it is smaller than the input.
+pub fn compress(data: &[u8], compression: Compression) -> (Vec<u8>, Compression) {
+    match compression {
+        Compression::None => (data.to_vec(), Compression::None),
+        Compression::Deflate => (compress_to_vec(data, DEFLATE_LEVEL), Compression::Deflate),
+        Compression::Auto => {
+            let deflated = compress_to_vec(data, DEFLATE_LEVEL);
+            if deflated.len() < data.len() {
+                (deflated, Compression::Deflate)
+            } else {
+                (data.to_vec(), Compression::None)
+            }
+        }
+    }
+}
+
+pub fn decompress(data: Vec<u8>, flags: u8, max_len: usize) -> Result<Vec<u8>, Error> {
+    if flags & FLAG_DEFLATE == 0 {
+        return Ok(data);
+    }
+
+    decompress_to_vec_with_limit(&data, max_len)
+        .map_err(|e| Error::Decompression(format!("{:?}", e.status)))
+}
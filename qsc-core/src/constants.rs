@@ -0,0 +1,25 @@
+pub const DEFAULT_QRCODE_MAX_DATA_CAPACITY: usize = 2953;
+pub const FRAME_MAGIC: [u8; 2] = *b"QS";
+pub const FRAME_PROTOCOL_VERSION: u8 = 1;
+pub const FRAME_HEADER_SIZE: usize = 18;
+// Frames are sized from untrusted headers before the manifest is checked.
+pub const MAX_SEQUENCE_FRAMES: usize = 1 << 20;
+pub const FLAG_FOUNTAIN: u8 = 0x01;
+pub const FLAG_MANIFEST: u8 = 0x02;
+pub const FLAG_DEFLATE: u8 = 0x04;
+pub const FLAG_ENCRYPTED: u8 = 0x08;
+pub const DEFAULT_QR_CODE_VERSION: i16 = 8;
+pub const MIN_QR_CODE_VERSION: i16 = 1;
+pub const MAX_QR_CODE_VERSION: i16 = 40;
+pub const MIN_MODULE_PIXELS: u32 = 2;
+pub const DEFAULT_QUIET_ZONE: u32 = 4;
+// A few modules already isolate the symbol; wider margins only shrink it.
+pub const MAX_QUIET_ZONE: u32 = 16;
+pub const DEFAULT_FRAME_DELAY_MS: u32 = 200;
+pub const DEFAULT_ANIMATION_SCALE: u32 = 4;
+pub const MAX_ANIMATION_SCALE: u32 = 32;
+// Every frame is rasterised at full size before encoding, 64 MiB at most.
+pub const MAX_ANIMATION_SIZE: u32 = 8192;
+pub const DEFAULT_PAPER_COLUMNS: usize = 3;
+// More columns than this leave cells too narrow for a scannable code.
+pub const MAX_PAPER_COLUMNS: usize = 8;
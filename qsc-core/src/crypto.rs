@@ -0,0 +1,130 @@
+use crate::error::Error;
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+pub const SALT_SIZE: usize = 16;
+pub const NONCE_SIZE: usize = 12;
+pub const ENCRYPTION_PARAMS_SIZE: usize = SALT_SIZE + NONCE_SIZE + 12;
+
+// Upper bounds on KDF costs read back from a manifest, so a forged frame
+// cannot make the receiver allocate more than a browser tab can spare.
+const MAX_M_COST: u32 = 64 * 1024;
+const MAX_T_COST: u32 = 64;
+const MAX_P_COST: u32 = 16;
+
+// Encryption parameters layout (big-endian):
+//   0..16  Argon2id salt
+//   16..28 ChaCha20-Poly1305 nonce
+//   28..32 memory cost (KiB)
+//   32..36 time cost
+//   36..40 parallelism
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncryptionParams {
+    pub salt: [u8; SALT_SIZE],
+    pub nonce: [u8; NONCE_SIZE],
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl EncryptionParams {
+    pub fn generate() -> Result<Self, Error> {
+        let mut salt = [0u8; SALT_SIZE];
+        let mut nonce = [0u8; NONCE_SIZE];
+        getrandom::getrandom(&mut salt).map_err(|e| Error::Random(e.to_string()))?;
+        getrandom::getrandom(&mut nonce).map_err(|e| Error::Random(e.to_string()))?;
+
+        Ok(Self {
+            salt,
+            nonce,
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(ENCRYPTION_PARAMS_SIZE);
+        bytes.extend_from_slice(&self.salt);
+        bytes.extend_from_slice(&self.nonce);
+        bytes.extend_from_slice(&self.m_cost.to_be_bytes());
+        bytes.extend_from_slice(&self.t_cost.to_be_bytes());
+        bytes.extend_from_slice(&self.p_cost.to_be_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < ENCRYPTION_PARAMS_SIZE {
+            return Err(Error::InvalidEncryptionParams);
+        }
+
+        let mut salt = [0u8; SALT_SIZE];
+        salt.copy_from_slice(&bytes[..SALT_SIZE]);
+        let mut nonce = [0u8; NONCE_SIZE];
+        nonce.copy_from_slice(&bytes[SALT_SIZE..SALT_SIZE + NONCE_SIZE]);
+        let read_u32 = |offset: usize| {
+            u32::from_be_bytes([
+                bytes[offset],
+                bytes[offset + 1],
+                bytes[offset + 2],
+                bytes[offset + 3],
+            ])
+        };
+
+        Ok(Self {
+            salt,
+            nonce,
+            m_cost: read_u32(SALT_SIZE + NONCE_SIZE),
+            t_cost: read_u32(SALT_SIZE + NONCE_SIZE + 4),
+            p_cost: read_u32(SALT_SIZE + NONCE_SIZE + 8),
+        })
+    }
+
+    fn derive_key(&self, passphrase: &str) -> Result<Key, Error> {
+        if self.m_cost > MAX_M_COST || self.t_cost > MAX_T_COST || self.p_cost > MAX_P_COST {
+            return Err(Error::InvalidEncryptionParams);
+        }
+
+        let params = Params::new(self.m_cost, self.t_cost, self.p_cost, Some(32))
+            .map_err(|_| Error::InvalidEncryptionParams)?;
+        let mut key = Key::default();
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &self.salt, &mut key)
+            .map_err(|_| Error::InvalidEncryptionParams)?;
+        Ok(key)
+    }
+}
+
+// Frame flags and the file length travel in the clear; sealing them as
+// associated data makes tampering with either fail decryption.
+pub fn associated_data(flags: u8, file_len: u64) -> [u8; 9] {
+    let mut aad = [0u8; 9];
+    aad[0] = flags;
+    aad[1..].copy_from_slice(&file_len.to_be_bytes());
+    aad
+}
+
+pub fn encrypt(
+    data: &[u8],
+    aad: &[u8],
+    passphrase: &str,
+    params: &EncryptionParams,
+) -> Result<Vec<u8>, Error> {
+    let key = params.derive_key(passphrase)?;
+    ChaCha20Poly1305::new(&key)
+        .encrypt(Nonce::from_slice(&params.nonce), Payload { msg: data, aad })
+        .map_err(|_| Error::EncryptionFailed)
+}
+
+pub fn decrypt(
+    data: &[u8],
+    aad: &[u8],
+    passphrase: &str,
+    params: &EncryptionParams,
+) -> Result<Vec<u8>, Error> {
+    let key = params.derive_key(passphrase)?;
+    ChaCha20Poly1305::new(&key)
+        .decrypt(Nonce::from_slice(&params.nonce), Payload { msg: data, aad })
+        .map_err(|_| Error::DecryptionFailed)
+}
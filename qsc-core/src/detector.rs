@@ -0,0 +1,659 @@
+use crate::binarize::{BitImage, binarize};
+use crate::reader::{ScannedCode, decode_modules, sampled_version};
+
+// Finder centres must be seen on this many rows before they are trusted.
+const MIN_FINDER_CONFIRMATIONS: u32 = 2;
+// Caps the triples tried on noisy photos; a page of codes stays well below.
+const MAX_FINDER_PATTERNS: usize = 96;
+const MAX_MODULE_SIZE_RATIO: f64 = 2.0;
+const MAX_LEG_RATIO: f64 = 1.5;
+const MAX_RIGHT_ANGLE_ERROR: f64 = 0.4;
+const ALIGNMENT_SEARCH_MODULES: [f64; 2] = [4.0, 16.0];
+const ALIGNMENT_SEARCH_STEP: f64 = 0.25;
+// Out of 25 template modules; perspective skews the outer ring first.
+const MIN_ALIGNMENT_SCORE: usize = 21;
+const ALIGNMENT_TEMPLATE_SIZE: usize = 25;
+const MAX_ALIGNMENT_CANDIDATES: usize = 4;
+const ALIGNMENT_REFINE_MODULES: f64 = 1.5;
+const DIMENSION_CANDIDATES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+impl Point {
+    fn distance(self, other: Point) -> f64 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FinderPattern {
+    center: Point,
+    module_size: f64,
+    count: u32,
+}
+
+// Finds and decodes every QR code in a greyscale image of `width` x `height`
+// pixels, one byte per pixel. A buffer of any other length holds no codes.
+pub fn scan_luma(pixels: &[u8], width: u32, height: u32) -> Vec<ScannedCode> {
+    let (width, height) = (width as usize, height as usize);
+    if width == 0 || height == 0 || width.checked_mul(height) != Some(pixels.len()) {
+        return Vec::new();
+    }
+    scan(&binarize(pixels, width, height))
+}
+
+fn scan(image: &BitImage) -> Vec<ScannedCode> {
+    let mut patterns: Vec<FinderPattern> = find_finder_patterns(image)
+        .into_iter()
+        .filter(|pattern| pattern.count >= MIN_FINDER_CONFIRMATIONS)
+        .collect();
+    patterns.sort_by_key(|pattern| std::cmp::Reverse(pattern.count));
+    patterns.truncate(MAX_FINDER_PATTERNS);
+
+    let mut triples = Vec::new();
+    for a in 0..patterns.len() {
+        for b in a + 1..patterns.len() {
+            for c in b + 1..patterns.len() {
+                if let Some(triple) = order_triple(&patterns, [a, b, c]) {
+                    triples.push(triple);
+                }
+            }
+        }
+    }
+    triples.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut used = vec![false; patterns.len()];
+    let mut codes = Vec::new();
+    for (_, triple) in triples {
+        if triple.iter().any(|index| used[*index]) {
+            continue;
+        }
+        let [top_left, top_right, bottom_left] = triple.map(|index| patterns[index]);
+        if let Some(code) = decode_at(image, top_left, top_right, bottom_left) {
+            triple.iter().for_each(|index| used[*index] = true);
+            codes.push(code);
+        }
+    }
+    codes
+}
+
+// Returns a distortion score and the triple as top-left, top-right and
+// bottom-left when the centres could be the corners of one symbol.
+fn order_triple(patterns: &[FinderPattern], triple: [usize; 3]) -> Option<(f64, [usize; 3])> {
+    let [a, b, c] = triple.map(|index| patterns[index]);
+    let sizes = [a.module_size, b.module_size, c.module_size];
+    let smallest = sizes.iter().copied().fold(f64::MAX, f64::min);
+    let largest = sizes.iter().copied().fold(0.0, f64::max);
+    if largest > smallest * MAX_MODULE_SIZE_RATIO {
+        return None;
+    }
+
+    // The corner opposite the longest side holds the right angle.
+    let sides = [
+        (b.center.distance(c.center), 0),
+        (a.center.distance(c.center), 1),
+        (a.center.distance(b.center), 2),
+    ];
+    let (_, corner) = sides.iter().copied().max_by(|x, y| x.0.total_cmp(&y.0))?;
+    let [corner, first, second] = match corner {
+        0 => [triple[0], triple[1], triple[2]],
+        1 => [triple[1], triple[0], triple[2]],
+        _ => [triple[2], triple[0], triple[1]],
+    };
+
+    let origin = patterns[corner].center;
+    let (p, q) = (patterns[first].center, patterns[second].center);
+    let (leg_p, leg_q) = (origin.distance(p), origin.distance(q));
+    let module_size = (a.module_size + b.module_size + c.module_size) / 3.0;
+    if leg_p.min(leg_q) < 10.0 * module_size || leg_p.max(leg_q) > leg_p.min(leg_q) * MAX_LEG_RATIO
+    {
+        return None;
+    }
+
+    let legs = leg_p * leg_p + leg_q * leg_q;
+    let hypotenuse = p.distance(q);
+    let angle_error = (hypotenuse * hypotenuse - legs).abs() / legs;
+    if angle_error > MAX_RIGHT_ANGLE_ERROR {
+        return None;
+    }
+
+    // With y pointing down, top-right follows top-left clockwise.
+    let cross = (p.x - origin.x) * (q.y - origin.y) - (p.y - origin.y) * (q.x - origin.x);
+    let ordered = if cross > 0.0 {
+        [corner, first, second]
+    } else {
+        [corner, second, first]
+    };
+    let score = angle_error + (leg_p - leg_q).abs() / leg_p.max(leg_q);
+    Some((score, ordered))
+}
+
+fn decode_at(
+    image: &BitImage,
+    top_left: FinderPattern,
+    top_right: FinderPattern,
+    bottom_left: FinderPattern,
+) -> Option<ScannedCode> {
+    let across = leg_module_size(image, top_left, top_right);
+    let down = leg_module_size(image, top_left, bottom_left);
+    let estimate = (top_left.center.distance(top_right.center) / across
+        + top_left.center.distance(bottom_left.center) / down)
+        / 2.0
+        + 7.0;
+
+    let mut dimensions: Vec<usize> = (1..=40).map(|version| 17 + 4 * version).collect();
+    dimensions.sort_by(|a, b| {
+        (*a as f64 - estimate)
+            .abs()
+            .total_cmp(&(*b as f64 - estimate).abs())
+    });
+    dimensions.truncate(DIMENSION_CANDIDATES);
+
+    let corners = [top_left.center, top_right.center, bottom_left.center];
+    let mut tried = Vec::new();
+    while let Some(dimension) = dimensions.first().copied() {
+        dimensions.remove(0);
+        if tried.contains(&dimension) {
+            continue;
+        }
+        tried.push(dimension);
+
+        for transform in transforms(image, corners, dimension) {
+            let Some(modules) = sample(image, &transform, dimension) else {
+                continue;
+            };
+            if let Some(code) = decode_either_way(&modules, dimension) {
+                return Some(code);
+            }
+            // Large versions carry their number; trust it over the estimate.
+            if let Some(version) = sampled_version(&modules, dimension) {
+                let actual = 17 + 4 * version as usize;
+                if !tried.contains(&actual) {
+                    dimensions.insert(0, actual);
+                }
+            }
+        }
+    }
+    None
+}
+
+// Mirrored captures sample as the transposed grid.
+fn decode_either_way(modules: &[bool], dimension: usize) -> Option<ScannedCode> {
+    decode_modules(modules, dimension).ok().or_else(|| {
+        let transposed: Vec<bool> = (0..dimension * dimension)
+            .map(|i| modules[(i % dimension) * dimension + i / dimension])
+            .collect();
+        decode_modules(&transposed, dimension).ok()
+    })
+}
+
+// Candidate module-to-image mappings: through the bottom-right alignment
+// pattern when one is found, then the parallelogram spanned by the finders.
+fn transforms(
+    image: &BitImage,
+    [top_left, top_right, bottom_left]: [Point; 3],
+    dimension: usize,
+) -> Vec<Homography> {
+    let far = dimension as f64 - 3.5;
+    let bottom_right = Point {
+        x: top_right.x + bottom_left.x - top_left.x,
+        y: top_right.y + bottom_left.y - top_left.y,
+    };
+    let finders = [
+        (Point { x: 3.5, y: 3.5 }, top_left),
+        (Point { x: far, y: 3.5 }, top_right),
+        (Point { x: 3.5, y: far }, bottom_left),
+    ];
+    let Some(affine) = Homography::from_points(&[
+        finders[0],
+        finders[1],
+        finders[2],
+        (Point { x: far, y: far }, bottom_right),
+    ]) else {
+        return Vec::new();
+    };
+
+    let mut transforms = Vec::new();
+    if dimension > 21 {
+        let alignment = Point {
+            x: dimension as f64 - 6.5,
+            y: dimension as f64 - 6.5,
+        };
+        let through = |found: Point| {
+            Homography::from_points(&[finders[0], finders[1], finders[2], (alignment, found)])
+        };
+        // A rough match fixes the perspective well enough to search again
+        // with the template drawn in the right shape.
+        let candidates =
+            find_alignment_patterns(image, &affine, alignment, &ALIGNMENT_SEARCH_MODULES);
+        for (score, found) in candidates {
+            let Some(transform) = through(found) else {
+                continue;
+            };
+            let refined =
+                find_alignment_patterns(image, &transform, alignment, &[ALIGNMENT_REFINE_MODULES])
+                    .into_iter()
+                    .next()
+                    .filter(|(refined, _)| *refined >= score)
+                    .and_then(|(_, found)| through(found));
+            transforms.extend(refined);
+            transforms.push(transform);
+        }
+    }
+    transforms.push(affine);
+    transforms
+}
+
+fn sample(image: &BitImage, transform: &Homography, dimension: usize) -> Option<Vec<bool>> {
+    let (width, height) = (image.width() as f64, image.height() as f64);
+    let mut modules = Vec::with_capacity(dimension * dimension);
+    for y in 0..dimension {
+        for x in 0..dimension {
+            let point = transform.map(Point {
+                x: x as f64 + 0.5,
+                y: y as f64 + 0.5,
+            });
+            if point.x < -1.0 || point.y < -1.0 || point.x > width || point.y > height {
+                return None;
+            }
+            let px = (point.x.max(0.0) as usize).min(image.width() - 1);
+            let py = (point.y.max(0.0) as usize).min(image.height() - 1);
+            modules.push(image.is_dark(px, py));
+        }
+    }
+    Some(modules)
+}
+
+// Projective map from module coordinates to image coordinates.
+struct Homography([f64; 8]);
+
+impl Homography {
+    // Solves the eight unknowns from four (module, image) correspondences.
+    fn from_points(pairs: &[(Point, Point); 4]) -> Option<Self> {
+        let mut rows = [[0.0f64; 9]; 8];
+        for (i, (from, to)) in pairs.iter().enumerate() {
+            rows[2 * i] = [
+                from.x,
+                from.y,
+                1.0,
+                0.0,
+                0.0,
+                0.0,
+                -from.x * to.x,
+                -from.y * to.x,
+                to.x,
+            ];
+            rows[2 * i + 1] = [
+                0.0,
+                0.0,
+                0.0,
+                from.x,
+                from.y,
+                1.0,
+                -from.x * to.y,
+                -from.y * to.y,
+                to.y,
+            ];
+        }
+
+        for column in 0..8 {
+            let pivot = (column..8)
+                .max_by(|a, b| rows[*a][column].abs().total_cmp(&rows[*b][column].abs()))?;
+            if rows[pivot][column].abs() < 1e-9 {
+                return None;
+            }
+            rows.swap(column, pivot);
+            let pivot_row = rows[column];
+            for (index, row) in rows.iter_mut().enumerate() {
+                if index == column {
+                    continue;
+                }
+                let factor = row[column] / pivot_row[column];
+                for (value, pivot_value) in row.iter_mut().zip(pivot_row).skip(column) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+
+        let mut h = [0.0; 8];
+        for (i, value) in h.iter_mut().enumerate() {
+            *value = rows[i][8] / rows[i][i];
+        }
+        Some(Homography(h))
+    }
+
+    fn map(&self, point: Point) -> Point {
+        let h = &self.0;
+        let w = h[6] * point.x + h[7] * point.y + 1.0;
+        Point {
+            x: (h[0] * point.x + h[1] * point.y + h[2]) / w,
+            y: (h[3] * point.x + h[4] * point.y + h[5]) / w,
+        }
+    }
+}
+
+// Row scan for the 1:1:3:1:1 dark-light-dark-light-dark finder signature,
+// confirmed vertically and horizontally through the candidate centre.
+fn find_finder_patterns(image: &BitImage) -> Vec<FinderPattern> {
+    let mut patterns: Vec<FinderPattern> = Vec::new();
+
+    for y in 0..image.height() {
+        let mut counts = [0usize; 5];
+        let mut state = 0;
+        for x in 0..image.width() {
+            if image.is_dark(x, y) {
+                if state % 2 == 1 {
+                    state += 1;
+                }
+                counts[state] += 1;
+            } else if state % 2 == 1 {
+                counts[state] += 1;
+            } else if state < 4 {
+                state += 1;
+                counts[state] += 1;
+            } else {
+                if is_finder_ratio(&counts)
+                    && let Some(pattern) = confirm_finder(image, &counts, x, y)
+                {
+                    merge_pattern(&mut patterns, pattern);
+                    counts = [0; 5];
+                    state = 0;
+                    continue;
+                }
+                counts = [counts[2], counts[3], counts[4], 1, 0];
+                state = 3;
+            }
+        }
+        if is_finder_ratio(&counts)
+            && let Some(pattern) = confirm_finder(image, &counts, image.width(), y)
+        {
+            merge_pattern(&mut patterns, pattern);
+        }
+    }
+    patterns
+}
+
+fn is_finder_ratio(counts: &[usize; 5]) -> bool {
+    let total: usize = counts.iter().sum();
+    if counts.contains(&0) || total < 7 {
+        return false;
+    }
+    let module = total as f64 / 7.0;
+    let tolerance = module / 2.0;
+    counts
+        .iter()
+        .zip([1.0, 1.0, 3.0, 1.0, 1.0])
+        .all(|(count, modules)| (module * modules - *count as f64).abs() < tolerance * modules)
+}
+
+fn center_from_end(counts: &[usize; 5], end: usize) -> f64 {
+    (end - counts[4] - counts[3]) as f64 - counts[2] as f64 / 2.0
+}
+
+fn confirm_finder(
+    image: &BitImage,
+    counts: &[usize; 5],
+    end: usize,
+    y: usize,
+) -> Option<FinderPattern> {
+    let total: usize = counts.iter().sum();
+    let center_x = center_from_end(counts, end);
+    let center_y = cross_check(image, center_x as usize, y, counts[2], total, true)?;
+    let center_x = cross_check(
+        image,
+        center_x as usize,
+        center_y as usize,
+        counts[2],
+        total,
+        false,
+    )?;
+
+    Some(FinderPattern {
+        center: Point {
+            x: center_x,
+            y: center_y,
+        },
+        module_size: total as f64 / 7.0,
+        count: 1,
+    })
+}
+
+// Measures the five runs through (x, y) along one axis and returns the
+// refined centre on that axis when they still look like a finder pattern.
+fn cross_check(
+    image: &BitImage,
+    x: usize,
+    y: usize,
+    max_count: usize,
+    original_total: usize,
+    vertical: bool,
+) -> Option<f64> {
+    let (start, limit) = if vertical {
+        (y, image.height())
+    } else {
+        (x, image.width())
+    };
+    let dark = |position: usize| {
+        if vertical {
+            image.is_dark(x, position)
+        } else {
+            image.is_dark(position, y)
+        }
+    };
+
+    let mut counts = [0usize; 5];
+    let mut position = start as isize;
+    while position >= 0 && dark(position as usize) {
+        counts[2] += 1;
+        position -= 1;
+    }
+    for (state, expect_dark) in [(1, false), (0, true)] {
+        while position >= 0 && dark(position as usize) == expect_dark && counts[state] <= max_count
+        {
+            counts[state] += 1;
+            position -= 1;
+        }
+        if counts[state] > max_count || (state == 1 && position < 0) {
+            return None;
+        }
+    }
+
+    let mut position = start + 1;
+    while position < limit && dark(position) {
+        counts[2] += 1;
+        position += 1;
+    }
+    for (state, expect_dark) in [(3, false), (4, true)] {
+        while position < limit && dark(position) == expect_dark && counts[state] <= max_count {
+            counts[state] += 1;
+            position += 1;
+        }
+        if counts[state] > max_count || (state == 3 && position == limit) {
+            return None;
+        }
+    }
+
+    let total: usize = counts.iter().sum();
+    if 5 * total.abs_diff(original_total) >= 2 * original_total || !is_finder_ratio(&counts) {
+        return None;
+    }
+    Some(center_from_end(&counts, position))
+}
+
+fn merge_pattern(patterns: &mut Vec<FinderPattern>, pattern: FinderPattern) {
+    let existing = patterns.iter_mut().find(|existing| {
+        (existing.center.x - pattern.center.x).abs() <= pattern.module_size
+            && (existing.center.y - pattern.center.y).abs() <= pattern.module_size
+            && ((existing.module_size - pattern.module_size).abs() <= 1.0
+                || (existing.module_size - pattern.module_size).abs() <= existing.module_size)
+    });
+
+    match existing {
+        Some(existing) => {
+            let count = existing.count as f64;
+            let weighted = |old: f64, new: f64| (old * count + new) / (count + 1.0);
+            existing.center = Point {
+                x: weighted(existing.center.x, pattern.center.x),
+                y: weighted(existing.center.y, pattern.center.y),
+            };
+            existing.module_size = weighted(existing.module_size, pattern.module_size);
+            existing.count += 1;
+        }
+        None => patterns.push(pattern),
+    }
+}
+
+// Module size along the line joining two finders, from the dark-light-dark
+// runs measured outwards from both centres; independent of rotation.
+fn leg_module_size(image: &BitImage, from: FinderPattern, to: FinderPattern) -> f64 {
+    let measure = |origin: FinderPattern, toward: Point| {
+        let length = origin.center.distance(toward);
+        let direction = Point {
+            x: (toward.x - origin.center.x) / length,
+            y: (toward.y - origin.center.y) / length,
+        };
+        let limit = 8.0 * origin.module_size;
+        let forward = finder_radius(image, origin.center, direction, limit)?;
+        let backward = finder_radius(
+            image,
+            origin.center,
+            Point {
+                x: -direction.x,
+                y: -direction.y,
+            },
+            limit,
+        )?;
+        Some((forward + backward) / 7.0)
+    };
+
+    let sizes: Vec<f64> = [measure(from, to.center), measure(to, from.center)]
+        .into_iter()
+        .flatten()
+        .collect();
+    if sizes.is_empty() {
+        return (from.module_size + to.module_size) / 2.0;
+    }
+    sizes.iter().sum::<f64>() / sizes.len() as f64
+}
+
+// Distance from a finder centre to the outer edge of its dark ring.
+fn finder_radius(image: &BitImage, origin: Point, direction: Point, limit: f64) -> Option<f64> {
+    let mut state = 0;
+    let mut distance = 0.0;
+    while distance < limit {
+        let x = origin.x + direction.x * distance;
+        let y = origin.y + direction.y * distance;
+        if x < 0.0 || y < 0.0 || x >= image.width() as f64 || y >= image.height() as f64 {
+            return (state == 2).then_some(distance);
+        }
+
+        let dark = image.is_dark(x as usize, y as usize);
+        state = match (state, dark) {
+            (0, false) => 1,
+            (1, true) => 2,
+            (2, false) => return Some(distance),
+            (state, _) => state,
+        };
+        distance += 0.5;
+    }
+    None
+}
+
+// Slides a 5x5 alignment pattern template over the neighbourhood of its
+// expected position, in module space so rotation does not matter. Perfect
+// matches close by win outright; otherwise the best few partial matches of a
+// wider search are returned, best first, as image points.
+fn find_alignment_patterns(
+    image: &BitImage,
+    transform: &Homography,
+    expected: Point,
+    radii: &[f64],
+) -> Vec<(usize, Point)> {
+    let mut scored = Vec::new();
+    for radius in radii {
+        let steps = (radius / ALIGNMENT_SEARCH_STEP) as i32;
+        scored = (-steps..=steps)
+            .flat_map(|dy| (-steps..=steps).map(move |dx| (dx, dy)))
+            .map(|(dx, dy)| {
+                let center = Point {
+                    x: expected.x + dx as f64 * ALIGNMENT_SEARCH_STEP,
+                    y: expected.y + dy as f64 * ALIGNMENT_SEARCH_STEP,
+                };
+                (alignment_template_score(image, transform, center), center)
+            })
+            .filter(|(score, _)| *score >= MIN_ALIGNMENT_SCORE)
+            .collect();
+        if scored
+            .iter()
+            .any(|(score, _)| *score == ALIGNMENT_TEMPLATE_SIZE)
+        {
+            break;
+        }
+    }
+    scored.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then(a.1.distance(expected).total_cmp(&b.1.distance(expected)))
+    });
+
+    // Average each cluster of equally good positions into one centre.
+    let mut candidates: Vec<(usize, Point)> = Vec::new();
+    for (score, center) in &scored {
+        if candidates.len() == MAX_ALIGNMENT_CANDIDATES {
+            break;
+        }
+        if candidates.iter().any(|(_, other)| {
+            transform.map(*center).distance(*other) < 2.0 * transform_scale(transform, *center)
+        }) {
+            continue;
+        }
+        let cluster: Vec<Point> = scored
+            .iter()
+            .filter(|(other, point)| other == score && point.distance(*center) < 1.0)
+            .map(|(_, point)| *point)
+            .collect();
+        let average = Point {
+            x: cluster.iter().map(|point| point.x).sum::<f64>() / cluster.len() as f64,
+            y: cluster.iter().map(|point| point.y).sum::<f64>() / cluster.len() as f64,
+        };
+        candidates.push((*score, transform.map(average)));
+    }
+    candidates
+}
+
+// Pixels per module around a point in module space.
+fn transform_scale(transform: &Homography, point: Point) -> f64 {
+    transform.map(point).distance(transform.map(Point {
+        x: point.x + 1.0,
+        y: point.y,
+    }))
+}
+
+// Counts the template modules that match: dark centre and outer ring, light
+// inner ring.
+fn alignment_template_score(image: &BitImage, transform: &Homography, center: Point) -> usize {
+    let mut score = 0;
+    for j in -2..=2i32 {
+        for i in -2..=2i32 {
+            let point = transform.map(Point {
+                x: center.x + i as f64,
+                y: center.y + j as f64,
+            });
+            if point.x < 0.0
+                || point.y < 0.0
+                || point.x >= image.width() as f64
+                || point.y >= image.height() as f64
+            {
+                continue;
+            }
+            let dark = i.abs().max(j.abs()) != 1;
+            if image.is_dark(point.x as usize, point.y as usize) == dark {
+                score += 1;
+            }
+        }
+    }
+    score
+}
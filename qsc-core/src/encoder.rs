@@ -0,0 +1,258 @@
+use crate::capacity::{encode_byte_mode, get_max_qr_capacity};
+use crate::compression::{Compression, compress};
+use crate::constants::{
+    DEFAULT_QR_CODE_VERSION, FLAG_ENCRYPTED, FLAG_FOUNTAIN, FLAG_MANIFEST, FRAME_HEADER_SIZE,
+    MAX_SEQUENCE_FRAMES,
+};
+use crate::crypto::{ENCRYPTION_PARAMS_SIZE, EncryptionParams, associated_data, encrypt};
+use crate::error::Error;
+use crate::fountain::{
+    DegreeDistribution, droplet_blocks, encode_droplet, fountain_block_count, fountain_block_size,
+};
+use crate::frame::{FrameHeader, encode_frame, session_id_for};
+use crate::manifest::{MANIFEST_SIZE, Manifest};
+use crate::render::validate_version;
+use qrcode::QrCode;
+use qrcode::{EcLevel, Version};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransferMode {
+    #[default]
+    Chunked,
+    Fountain,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodeOptions {
+    pub version: Version,
+    pub ec_level: EcLevel,
+    pub session_id: Option<u32>,
+    pub mode: TransferMode,
+    pub compression: Compression,
+    pub passphrase: Option<String>,
+}
+
+impl Default for EncodeOptions {
+    fn default() -> Self {
+        Self {
+            version: Version::Normal(DEFAULT_QR_CODE_VERSION),
+            ec_level: EcLevel::L,
+            session_id: None,
+            mode: TransferMode::Chunked,
+            compression: Compression::Auto,
+            passphrase: None,
+        }
+    }
+}
+
+pub fn parse_ec_level(value: &str) -> Option<EcLevel> {
+    match value.to_ascii_uppercase().as_str() {
+        "L" => Some(EcLevel::L),
+        "M" => Some(EcLevel::M),
+        "Q" => Some(EcLevel::Q),
+        "H" => Some(EcLevel::H),
+        _ => None,
+    }
+}
+
+#[derive(Clone)]
+pub struct Frame {
+    pub header: FrameHeader,
+    pub payload: Vec<u8>,
+    pub code: QrCode,
+}
+
+pub struct Encoder {
+    data: Vec<u8>,
+    options: EncodeOptions,
+    session_id: u32,
+    chunk_capacity: usize,
+    frame_count: usize,
+    distribution: Option<DegreeDistribution>,
+    manifest: Manifest,
+    compression: Compression,
+    flags: u8,
+}
+
+impl Encoder {
+    pub fn new(data: impl Into<Vec<u8>>, options: EncodeOptions) -> Result<Self, Error> {
+        validate_version(options.version)?;
+
+        let original = data.into();
+        let max_capacity = get_max_qr_capacity(options.version, options.ec_level);
+        let manifest_len = match options.passphrase {
+            Some(_) => MANIFEST_SIZE + ENCRYPTION_PARAMS_SIZE,
+            None => MANIFEST_SIZE,
+        };
+
+        let chunk_capacity = max_capacity
+            .checked_sub(FRAME_HEADER_SIZE)
+            .filter(|capacity| *capacity >= manifest_len)
+            .ok_or(Error::CapacityTooSmall {
+                capacity: max_capacity,
+            })?;
+
+        let (data, compression) = compress(&original, options.compression);
+        let mut flags = compression.flags();
+
+        let (data, manifest) = match &options.passphrase {
+            Some(passphrase) => {
+                flags |= FLAG_ENCRYPTED;
+                let params = EncryptionParams::generate()?;
+                let aad = associated_data(flags, original.len() as u64);
+                let ciphertext = encrypt(&data, &aad, passphrase, &params)?;
+                let manifest = Manifest::for_ciphertext(original.len(), &ciphertext, params);
+                (ciphertext, manifest)
+            }
+            None => (data, Manifest::for_data(&original)),
+        };
+        let session_id = options.session_id.unwrap_or_else(|| session_id_for(&data));
+
+        let (frame_count, distribution) = match options.mode {
+            TransferMode::Chunked => (data.len().div_ceil(chunk_capacity).max(1), None),
+            TransferMode::Fountain => {
+                let block_size = fountain_block_size(data.len(), chunk_capacity);
+                let block_count = fountain_block_count(data.len(), block_size);
+                (
+                    block_count,
+                    Some(DegreeDistribution::robust_soliton(block_count)),
+                )
+            }
+        };
+        if frame_count > MAX_SEQUENCE_FRAMES {
+            return Err(Error::TooManyFrames {
+                needed: frame_count,
+                max: MAX_SEQUENCE_FRAMES,
+            });
+        }
+
+        Ok(Self {
+            data,
+            options,
+            session_id,
+            chunk_capacity,
+            frame_count,
+            distribution,
+            manifest,
+            compression,
+            flags,
+        })
+    }
+
+    pub fn session_id(&self) -> u32 {
+        self.session_id
+    }
+
+    pub fn chunk_capacity(&self) -> usize {
+        self.chunk_capacity
+    }
+
+    pub fn mode(&self) -> TransferMode {
+        self.options.mode
+    }
+
+    // The codec actually applied, never `Auto`.
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.manifest.encryption.is_some()
+    }
+
+    pub fn stream_len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    // Data frames in one pass of the sequence, excluding the manifest; in
+    // fountain mode this is the number of source blocks, and `frame` accepts
+    // any seed beyond it.
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    pub fn frame_payload(&self, index: usize) -> Vec<u8> {
+        match &self.distribution {
+            Some(distribution) => self.droplet_payload(index as u32, distribution),
+            None => self.chunk_payload(index),
+        }
+    }
+
+    fn droplet_payload(&self, seed: u32, distribution: &DegreeDistribution) -> Vec<u8> {
+        let block_size = fountain_block_size(self.data.len(), self.chunk_capacity);
+        let blocks = droplet_blocks(self.session_id, seed, self.frame_count, distribution);
+        let droplet = encode_droplet(&self.data, block_size, &blocks);
+
+        let header = FrameHeader::new(
+            self.session_id,
+            seed,
+            self.data.len() as u32,
+            block_size as u16,
+        )
+        .with_flags(FLAG_FOUNTAIN | self.flags);
+        encode_frame(&header, &droplet)
+    }
+
+    fn chunk_payload(&self, index: usize) -> Vec<u8> {
+        let chunk_start = (index * self.chunk_capacity).min(self.data.len());
+        let chunk_end = ((index + 1) * self.chunk_capacity).min(self.data.len());
+        let chunk_data = &self.data[chunk_start..chunk_end];
+
+        let header = FrameHeader::new(
+            self.session_id,
+            index as u32,
+            self.frame_count as u32,
+            chunk_data.len() as u16,
+        )
+        .with_flags(self.flags);
+        encode_frame(&header, chunk_data)
+    }
+
+    pub fn manifest_payload(&self) -> Vec<u8> {
+        let manifest = self.manifest.to_bytes();
+        let header = FrameHeader::new(self.session_id, 0, 1, manifest.len() as u16)
+            .with_flags(FLAG_MANIFEST | self.flags);
+        encode_frame(&header, &manifest)
+    }
+
+    pub fn manifest_frame(&self) -> Result<Frame, Error> {
+        self.build_frame(self.manifest_payload())
+    }
+
+    pub fn frame(&self, index: usize) -> Result<Frame, Error> {
+        self.build_frame(self.frame_payload(index))
+    }
+
+    fn build_frame(&self, payload: Vec<u8>) -> Result<Frame, Error> {
+        let header = FrameHeader::from_bytes(&payload)?;
+        let code = encode_byte_mode(&payload, self.options.version, self.options.ec_level)?;
+
+        Ok(Frame {
+            header,
+            payload,
+            code,
+        })
+    }
+
+    // The manifest leads every pass; unbounded in fountain mode.
+    pub fn frames(&self) -> impl Iterator<Item = Result<Frame, Error>> + '_ {
+        let passes = match self.options.mode {
+            TransferMode::Chunked => 1,
+            TransferMode::Fountain => u32::MAX as usize / self.frame_count,
+        };
+        (0..passes).flat_map(move |pass| {
+            let start = pass * self.frame_count;
+            std::iter::once(self.manifest_frame())
+                .chain((start..start + self.frame_count).map(|index| self.frame(index)))
+        })
+    }
+}
+
+pub fn encode(data: &[u8], options: &EncodeOptions) -> Result<Vec<Frame>, Error> {
+    let encoder = Encoder::new(data, options.clone())?;
+    encoder.frames().take(encoder.frame_count() + 1).collect()
+}
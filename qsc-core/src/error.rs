@@ -0,0 +1,162 @@
+use qrcode::Version;
+use qrcode::types::QrError;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Qr(QrError),
+    CapacityTooSmall { capacity: usize },
+    FrameTooShort { len: usize },
+    InvalidMagic,
+    InvalidHeader(String),
+    UnsupportedProtocolVersion(u8),
+    TruncatedPayload { expected: usize, actual: usize },
+    SessionMismatch { expected: u32, actual: u32 },
+    TotalMismatch { expected: u32, actual: u32 },
+    FrameIndexOutOfRange { index: u32, total: u32 },
+    Incomplete { missing: usize },
+    BlockSizeMismatch { expected: usize, actual: usize },
+    ModeMismatch,
+    CompressionMismatch,
+    InvalidManifest { len: usize },
+    MissingManifest,
+    LengthMismatch { expected: u64, actual: u64 },
+    DigestMismatch { expected: String, actual: String },
+    Decompression(String),
+    ImageEncoding(String),
+    Archive(String),
+    EmptySequence,
+    Random(String),
+    InvalidEncryptionParams,
+    EncryptionFailed,
+    DecryptionFailed,
+    PassphraseRequired,
+    TooManySymbols { needed: usize, max: usize },
+    UnsupportedVersion(Version),
+    CanvasTooSmall { canvas_size: u32, min_size: u32 },
+    QrDecode(String),
+    ParityMismatch { expected: u8, actual: u8 },
+    TooManyFrames { needed: usize, max: usize },
+    QuietZoneTooLarge(u32),
+    TooManyPendingDroplets { max: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Qr(e) => write!(f, "QR code error: {}", e),
+            Error::CapacityTooSmall { capacity } => write!(
+                f,
+                "QR capacity of {} bytes is too small for the frame header",
+                capacity
+            ),
+            Error::FrameTooShort { len } => write!(f, "Frame too short: {} bytes", len),
+            Error::InvalidMagic => write!(f, "Invalid frame magic"),
+            Error::InvalidHeader(reason) => write!(f, "Invalid frame header: {}", reason),
+            Error::UnsupportedProtocolVersion(version) => {
+                write!(f, "Unsupported frame protocol version: {}", version)
+            }
+            Error::TruncatedPayload { expected, actual } => write!(
+                f,
+                "Frame payload truncated: expected {} bytes, got {}",
+                expected, actual
+            ),
+            Error::SessionMismatch { expected, actual } => write!(
+                f,
+                "Frame belongs to session {:08x}, expected {:08x}",
+                actual, expected
+            ),
+            Error::TotalMismatch { expected, actual } => write!(
+                f,
+                "Frame announces {} total frames, expected {}",
+                actual, expected
+            ),
+            Error::FrameIndexOutOfRange { index, total } => {
+                write!(f, "Frame index {} out of range for {} frames", index, total)
+            }
+            Error::Incomplete { missing } => {
+                write!(f, "Sequence incomplete: {} frames missing", missing)
+            }
+            Error::BlockSizeMismatch { expected, actual } => write!(
+                f,
+                "Fountain droplet has {} bytes, expected {}",
+                actual, expected
+            ),
+            Error::ModeMismatch => {
+                write!(
+                    f,
+                    "Chunked and fountain frames cannot be mixed in one session"
+                )
+            }
+            Error::CompressionMismatch => {
+                write!(f, "Frame compression flag differs from earlier frames")
+            }
+            Error::InvalidManifest { len } => write!(f, "Invalid manifest: {} bytes", len),
+            Error::MissingManifest => write!(f, "Manifest frame has not been received"),
+            Error::LengthMismatch { expected, actual } => write!(
+                f,
+                "Reassembled file is {} bytes, manifest expects {}",
+                actual, expected
+            ),
+            Error::DigestMismatch { expected, actual } => write!(
+                f,
+                "SHA-256 mismatch: manifest expects {}, reassembled file is {}",
+                expected, actual
+            ),
+            Error::Decompression(status) => write!(f, "Failed to inflate payload: {}", status),
+            Error::ImageEncoding(reason) => write!(f, "Failed to encode image: {}", reason),
+            Error::Archive(reason) => write!(f, "Failed to build archive: {}", reason),
+            Error::EmptySequence => write!(f, "Sequence has no frames"),
+            Error::Random(e) => write!(f, "Failed to generate random bytes: {}", e),
+            Error::InvalidEncryptionParams => write!(f, "Invalid encryption parameters"),
+            Error::EncryptionFailed => write!(f, "Failed to encrypt payload"),
+            Error::DecryptionFailed => {
+                write!(f, "Decryption failed: wrong passphrase or tampered data")
+            }
+            Error::PassphraseRequired => write!(f, "Sequence is encrypted, passphrase required"),
+            Error::TooManySymbols { needed, max } => write!(
+                f,
+                "Data needs {} structured append symbols, at most {} are allowed",
+                needed, max
+            ),
+            Error::UnsupportedVersion(version) => {
+                write!(f, "Unsupported QR version {:?}, expected 1 to 40", version)
+            }
+            Error::CanvasTooSmall {
+                canvas_size,
+                min_size,
+            } => write!(
+                f,
+                "Canvas of {} px cannot resolve the modules, at least {} px needed",
+                canvas_size, min_size
+            ),
+            Error::QrDecode(reason) => write!(f, "Failed to decode QR code: {}", reason),
+            Error::ParityMismatch { expected, actual } => write!(
+                f,
+                "Structured append parity is {:02x}, symbols announce {:02x}",
+                actual, expected
+            ),
+            Error::TooManyFrames { needed, max } => write!(
+                f,
+                "Data needs {} frames, at most {} are supported",
+                needed, max
+            ),
+            Error::QuietZoneTooLarge(quiet_zone) => {
+                write!(f, "Quiet zone of {} modules is too large", quiet_zone)
+            }
+            Error::TooManyPendingDroplets { max } => write!(
+                f,
+                "More than {} fountain droplets are waiting on unsolved blocks",
+                max
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<QrError> for Error {
+    fn from(e: QrError) -> Self {
+        Error::Qr(e)
+    }
+}
@@ -0,0 +1,321 @@
+use crate::error::Error;
+
+const SOLITON_C: f64 = 0.1;
+const SOLITON_DELTA: f64 = 0.05;
+// Honest streams peel long before this many droplets wait on unsolved blocks;
+// the cap keeps a hostile one from growing the decoder without bound.
+const PENDING_DROPLETS_PER_BLOCK: usize = 4;
+const MIN_PENDING_DROPLETS: usize = 64;
+
+// Natural log built from the integer log2 so the degree table is bit-identical
+// between native and wasm builds, which do not share a libm.
+fn approx_ln(x: f64) -> f64 {
+    if x < 2.0 {
+        return 0.0;
+    }
+    (x as u64).ilog2() as f64 * std::f64::consts::LN_2
+}
+
+// Deterministic splitmix64 stream, seeded from the session and droplet seed.
+struct DropletRng(u64);
+
+impl DropletRng {
+    fn new(session_id: u32, seed: u32) -> Self {
+        Self(((session_id as u64) << 32) | seed as u64)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+// Robust soliton distribution over degrees 1..=block_count, stored as a CDF.
+#[derive(Debug, Clone)]
+pub struct DegreeDistribution {
+    cdf: Vec<f64>,
+}
+
+impl DegreeDistribution {
+    pub fn robust_soliton(block_count: usize) -> Self {
+        let k = block_count.max(1) as f64;
+        let r = (SOLITON_C * approx_ln(k / SOLITON_DELTA) * k.sqrt()).max(1.0);
+        let spike = ((k / r) as usize).clamp(1, block_count.max(1));
+
+        let mut weights = Vec::with_capacity(block_count.max(1));
+        for degree in 1..=block_count.max(1) {
+            let d = degree as f64;
+            let rho = if degree == 1 {
+                1.0 / k
+            } else {
+                1.0 / (d * (d - 1.0))
+            };
+            let tau = if degree < spike {
+                r / (d * k)
+            } else if degree == spike {
+                r * approx_ln(r / SOLITON_DELTA) / k
+            } else {
+                0.0
+            };
+            weights.push(rho + tau);
+        }
+
+        let sum: f64 = weights.iter().sum();
+        let mut acc = 0.0;
+        let cdf = weights
+            .into_iter()
+            .map(|weight| {
+                acc += weight / sum;
+                acc
+            })
+            .collect();
+
+        Self { cdf }
+    }
+
+    fn sample(&self, rng: &mut DropletRng) -> usize {
+        let r = rng.next_f64();
+        self.cdf
+            .iter()
+            .position(|&p| r < p)
+            .unwrap_or(self.cdf.len() - 1)
+            + 1
+    }
+}
+
+// The first `block_count` seeds are systematic (one source block each), so a
+// receiver that catches a full pass never has to solve anything.
+pub fn droplet_blocks(
+    session_id: u32,
+    seed: u32,
+    block_count: usize,
+    distribution: &DegreeDistribution,
+) -> Vec<usize> {
+    if (seed as usize) < block_count {
+        return vec![seed as usize];
+    }
+
+    let mut rng = DropletRng::new(session_id, seed);
+    let degree = distribution.sample(&mut rng).min(block_count);
+
+    // Floyd's algorithm for `degree` distinct indices.
+    let mut blocks = Vec::with_capacity(degree);
+    for upper in block_count - degree..block_count {
+        let candidate = rng.next_below(upper + 1);
+        if blocks.contains(&candidate) {
+            blocks.push(upper);
+        } else {
+            blocks.push(candidate);
+        }
+    }
+    blocks.sort_unstable();
+    blocks
+}
+
+pub fn encode_droplet(data: &[u8], block_size: usize, blocks: &[usize]) -> Vec<u8> {
+    let mut droplet = vec![0u8; block_size];
+    for &block in blocks {
+        let start = (block * block_size).min(data.len());
+        let end = ((block + 1) * block_size).min(data.len());
+        xor_into(&mut droplet, &data[start..end]);
+    }
+    droplet
+}
+
+fn xor_into(target: &mut [u8], source: &[u8]) {
+    for (t, s) in target.iter_mut().zip(source) {
+        *t ^= s;
+    }
+}
+
+struct PendingDroplet {
+    blocks: Vec<usize>,
+    data: Vec<u8>,
+}
+
+// Peeling (belief-propagation) decoder for LT droplets.
+pub struct FountainDecoder {
+    session_id: u32,
+    data_len: usize,
+    block_size: usize,
+    distribution: DegreeDistribution,
+    blocks: Vec<Option<Vec<u8>>>,
+    solved: usize,
+    pending: Vec<Option<PendingDroplet>>,
+    // Slots in `pending` whose droplet was solved, reused before it grows.
+    free_slots: Vec<usize>,
+    waiting: Vec<Vec<usize>>,
+    seen_seeds: std::collections::HashSet<u32>,
+}
+
+impl FountainDecoder {
+    pub fn new(session_id: u32, data_len: usize, block_size: usize) -> Self {
+        let block_count = fountain_block_count(data_len, block_size);
+        Self {
+            session_id,
+            data_len,
+            block_size,
+            distribution: DegreeDistribution::robust_soliton(block_count),
+            blocks: vec![None; block_count],
+            solved: 0,
+            pending: Vec::new(),
+            free_slots: Vec::new(),
+            waiting: vec![Vec::new(); block_count],
+            seen_seeds: std::collections::HashSet::new(),
+        }
+    }
+
+    pub fn data_len(&self) -> usize {
+        self.data_len
+    }
+
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    pub fn solved(&self) -> usize {
+        self.solved
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.solved == self.blocks.len()
+    }
+
+    // Droplets still covering two or more unsolved blocks.
+    pub fn pending_droplets(&self) -> usize {
+        self.pending.len() - self.free_slots.len()
+    }
+
+    pub fn max_pending_droplets(&self) -> usize {
+        self.blocks
+            .len()
+            .saturating_mul(PENDING_DROPLETS_PER_BLOCK)
+            .max(MIN_PENDING_DROPLETS)
+    }
+
+    pub fn missing_blocks(&self) -> Vec<u32> {
+        self.blocks
+            .iter()
+            .enumerate()
+            .filter(|(_, block)| block.is_none())
+            .map(|(index, _)| index as u32)
+            .collect()
+    }
+
+    // Returns false when the seed has already been seen.
+    pub fn push(&mut self, seed: u32, payload: &[u8]) -> Result<bool, Error> {
+        if payload.len() != self.block_size {
+            return Err(Error::BlockSizeMismatch {
+                expected: self.block_size,
+                actual: payload.len(),
+            });
+        }
+
+        if !self.seen_seeds.insert(seed) {
+            return Ok(false);
+        }
+
+        let mut blocks =
+            droplet_blocks(self.session_id, seed, self.blocks.len(), &self.distribution);
+        let mut data = payload.to_vec();
+
+        blocks.retain(|&block| match &self.blocks[block] {
+            Some(known) => {
+                xor_into(&mut data, known);
+                false
+            }
+            None => true,
+        });
+
+        match blocks.len() {
+            0 => {}
+            1 => self.solve(blocks[0], data),
+            _ => {
+                let max = self.max_pending_droplets();
+                if self.pending_droplets() >= max {
+                    // Not kept, so a repeat of it may still be used later.
+                    self.seen_seeds.remove(&seed);
+                    return Err(Error::TooManyPendingDroplets { max });
+                }
+
+                let id = self.free_slots.pop().unwrap_or_else(|| {
+                    self.pending.push(None);
+                    self.pending.len() - 1
+                });
+                for &block in &blocks {
+                    self.waiting[block].push(id);
+                }
+                self.pending[id] = Some(PendingDroplet { blocks, data });
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn solve(&mut self, block: usize, data: Vec<u8>) {
+        let mut queue = vec![(block, data)];
+
+        while let Some((block, data)) = queue.pop() {
+            if self.blocks[block].is_some() {
+                continue;
+            }
+
+            for id in std::mem::take(&mut self.waiting[block]) {
+                // A reused slot may hold a newer droplet without this block.
+                let Some(droplet) = self.pending[id]
+                    .as_mut()
+                    .filter(|droplet| droplet.blocks.contains(&block))
+                else {
+                    continue;
+                };
+
+                xor_into(&mut droplet.data, &data);
+                droplet.blocks.retain(|&b| b != block);
+
+                if droplet.blocks.len() == 1 {
+                    let droplet = self.pending[id].take().expect("pending droplet");
+                    self.free_slots.push(id);
+                    queue.push((droplet.blocks[0], droplet.data));
+                }
+            }
+
+            self.blocks[block] = Some(data);
+            self.solved += 1;
+        }
+    }
+
+    pub fn finish(self) -> Result<Vec<u8>, Error> {
+        if !self.is_complete() {
+            return Err(Error::Incomplete {
+                missing: self.blocks.len() - self.solved,
+            });
+        }
+
+        let mut data: Vec<u8> = self.blocks.into_iter().flatten().flatten().collect();
+        data.truncate(self.data_len);
+        Ok(data)
+    }
+}
+
+pub fn fountain_block_size(data_len: usize, chunk_capacity: usize) -> usize {
+    chunk_capacity.min(data_len).max(1)
+}
+
+pub fn fountain_block_count(data_len: usize, block_size: usize) -> usize {
+    data_len.div_ceil(block_size.max(1)).max(1)
+}
@@ -0,0 +1,140 @@
+use crate::constants::{
+    FLAG_DEFLATE, FLAG_ENCRYPTED, FLAG_FOUNTAIN, FLAG_MANIFEST, FRAME_HEADER_SIZE, FRAME_MAGIC,
+    FRAME_PROTOCOL_VERSION,
+};
+use crate::error::Error;
+
+// Frame layout (big-endian):
+//   0..2   magic "QS"
+//   2      protocol version
+//   3      flags
+//   4..8   session id
+//   8..12  frame index (droplet seed in fountain mode)
+//   12..16 total frames (source length in bytes in fountain mode)
+//   16..18 payload length
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameHeader {
+    pub version: u8,
+    pub flags: u8,
+    pub session_id: u32,
+    pub index: u32,
+    pub total: u32,
+    pub payload_len: u16,
+}
+
+impl FrameHeader {
+    pub fn new(session_id: u32, index: u32, total: u32, payload_len: u16) -> Self {
+        Self {
+            version: FRAME_PROTOCOL_VERSION,
+            flags: 0,
+            session_id,
+            index,
+            total,
+            payload_len,
+        }
+    }
+
+    pub fn with_flags(mut self, flags: u8) -> Self {
+        self.flags |= flags;
+        self
+    }
+
+    pub fn is_fountain(&self) -> bool {
+        self.flags & FLAG_FOUNTAIN != 0
+    }
+
+    pub fn is_manifest(&self) -> bool {
+        self.flags & FLAG_MANIFEST != 0
+    }
+
+    pub fn is_deflated(&self) -> bool {
+        self.flags & FLAG_DEFLATE != 0
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.flags & FLAG_ENCRYPTED != 0
+    }
+
+    pub fn to_bytes(&self) -> [u8; FRAME_HEADER_SIZE] {
+        let mut bytes = [0u8; FRAME_HEADER_SIZE];
+        bytes[0..2].copy_from_slice(&FRAME_MAGIC);
+        bytes[2] = self.version;
+        bytes[3] = self.flags;
+        bytes[4..8].copy_from_slice(&self.session_id.to_be_bytes());
+        bytes[8..12].copy_from_slice(&self.index.to_be_bytes());
+        bytes[12..16].copy_from_slice(&self.total.to_be_bytes());
+        bytes[16..18].copy_from_slice(&self.payload_len.to_be_bytes());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < FRAME_HEADER_SIZE {
+            return Err(Error::FrameTooShort { len: bytes.len() });
+        }
+
+        if bytes[0..2] != FRAME_MAGIC {
+            return Err(Error::InvalidMagic);
+        }
+
+        let version = bytes[2];
+        if version != FRAME_PROTOCOL_VERSION {
+            return Err(Error::UnsupportedProtocolVersion(version));
+        }
+
+        Ok(Self {
+            version,
+            flags: bytes[3],
+            session_id: u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+            index: u32::from_be_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]),
+            total: u32::from_be_bytes([bytes[12], bytes[13], bytes[14], bytes[15]]),
+            payload_len: u16::from_be_bytes([bytes[16], bytes[17]]),
+        })
+    }
+}
+
+pub fn encode_frame(header: &FrameHeader, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(FRAME_HEADER_SIZE + payload.len());
+    frame.extend_from_slice(&header.to_bytes());
+    frame.extend_from_slice(payload);
+    frame
+}
+
+pub fn decode_frame(frame: &[u8]) -> Result<(FrameHeader, &[u8]), Error> {
+    let header = FrameHeader::from_bytes(frame)?;
+    let payload_end = FRAME_HEADER_SIZE + header.payload_len as usize;
+
+    if frame.len() < payload_end {
+        return Err(Error::TruncatedPayload {
+            expected: header.payload_len as usize,
+            actual: frame.len() - FRAME_HEADER_SIZE,
+        });
+    }
+
+    Ok((header, &frame[FRAME_HEADER_SIZE..payload_end]))
+}
+
+// FNV-1a over the file contents, so re-encoding the same file yields the same session.
+pub fn session_id_for(data: &[u8]) -> u32 {
+    let mut hash: u32 = 0x811c_9dc5;
+    for byte in data.iter().chain(&(data.len() as u64).to_be_bytes()) {
+        hash ^= *byte as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash
+}
+
+// CRC-32 of the full frame bytes, printed next to codes so a human can tell
+// frames apart and spot a misprint.
+pub fn frame_checksum(frame: &[u8]) -> u32 {
+    crc32fast::hash(frame)
+}
+
+// Leading four hex digits of `frame_checksum`, enough to tell frames apart.
+pub fn short_checksum(frame: &[u8]) -> String {
+    format!("{:08x}", frame_checksum(frame))[..4].to_string()
+}
+
+// Human-readable label such as `12 / 40 · a3f9`; `index` is zero-based.
+pub fn frame_caption(index: usize, total: usize, frame: &[u8]) -> String {
+    format!("{} / {} · {}", index + 1, total, short_checksum(frame))
+}
@@ -0,0 +1,57 @@
+mod animation;
+mod archive;
+mod binarize;
+mod capacity;
+mod compression;
+mod constants;
+mod crypto;
+mod detector;
+mod encoder;
+mod error;
+mod fountain;
+mod frame;
+mod manifest;
+mod matrix;
+mod pdf;
+mod png;
+mod reader;
+mod reassembler;
+mod reed_solomon;
+mod render;
+mod structured_append;
+mod svg;
+mod terminal;
+
+pub use animation::{AnimationFormat, AnimationOptions, render_animation};
+pub use archive::{build_png_zip, build_zip};
+pub use capacity::{encode_byte_mode, get_max_qr_capacity};
+pub use compression::Compression;
+pub use constants::*;
+pub use crypto::EncryptionParams;
+pub use detector::scan_luma;
+pub use encoder::{EncodeOptions, Encoder, Frame, TransferMode, encode, parse_ec_level};
+pub use error::Error;
+pub use fountain::FountainDecoder;
+pub use frame::{
+    FrameHeader, decode_frame, encode_frame, frame_caption, frame_checksum, session_id_for,
+    short_checksum,
+};
+pub use manifest::{MANIFEST_SIZE, Manifest, to_hex};
+pub use matrix::Matrix;
+pub use pdf::{PageSize, PaperFrame, PaperOptions, render_pdf};
+pub use png::{encode_png, render_png};
+pub use qrcode;
+pub use reader::{ScannedCode, StructuredAppendHeader, decode_symbol};
+pub use reassembler::{Progress, PushOutcome, Reassembler};
+pub use render::{
+    check_canvas_size, frame_file_name, min_canvas_size, module_pixels, rasterize_luma,
+    rasterize_rgba, symbol_offset, validate_version,
+};
+pub use structured_append::{
+    MAX_STRUCTURED_APPEND_SYMBOLS, Symbol, encode_structured_append, join_structured_append,
+    structured_append_symbol_capacity,
+};
+pub use svg::{render_svg, render_svg_sheet};
+pub use terminal::{TerminalOptions, render_terminal};
+#[cfg(not(target_arch = "wasm32"))]
+pub use terminal::{play_encoder, play_terminal};
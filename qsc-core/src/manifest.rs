@@ -0,0 +1,100 @@
+use crate::crypto::{ENCRYPTION_PARAMS_SIZE, EncryptionParams};
+use crate::error::Error;
+use sha2::{Digest, Sha256};
+
+pub const MANIFEST_SIZE: usize = 40;
+
+// Manifest payload layout (big-endian):
+//   0..8   original file length
+//   8..40  SHA-256 of the original file, or of the ciphertext when encrypted
+//   40..80 encryption parameters, only present when encrypted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Manifest {
+    pub file_len: u64,
+    pub sha256: [u8; 32],
+    pub encryption: Option<EncryptionParams>,
+}
+
+impl Manifest {
+    pub fn for_data(data: &[u8]) -> Self {
+        Self {
+            file_len: data.len() as u64,
+            sha256: Sha256::digest(data).into(),
+            encryption: None,
+        }
+    }
+
+    // Digesting the ciphertext keeps the plaintext hash out of the clear, where
+    // it would let anyone confirm guesses without paying for the KDF.
+    pub fn for_ciphertext(file_len: usize, ciphertext: &[u8], params: EncryptionParams) -> Self {
+        Self {
+            file_len: file_len as u64,
+            sha256: Sha256::digest(ciphertext).into(),
+            encryption: Some(params),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(MANIFEST_SIZE + ENCRYPTION_PARAMS_SIZE);
+        bytes.extend_from_slice(&self.file_len.to_be_bytes());
+        bytes.extend_from_slice(&self.sha256);
+        if let Some(params) = &self.encryption {
+            bytes.extend_from_slice(&params.to_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < MANIFEST_SIZE {
+            return Err(Error::InvalidManifest { len: bytes.len() });
+        }
+
+        let mut file_len = [0u8; 8];
+        file_len.copy_from_slice(&bytes[0..8]);
+        let mut sha256 = [0u8; 32];
+        sha256.copy_from_slice(&bytes[8..40]);
+        let encryption = if bytes.len() > MANIFEST_SIZE {
+            Some(EncryptionParams::from_bytes(&bytes[MANIFEST_SIZE..])?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            file_len: u64::from_be_bytes(file_len),
+            sha256,
+            encryption,
+        })
+    }
+
+    pub fn verify_len(&self, data: &[u8]) -> Result<(), Error> {
+        if data.len() as u64 != self.file_len {
+            return Err(Error::LengthMismatch {
+                expected: self.file_len,
+                actual: data.len() as u64,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn verify_digest(&self, bytes: &[u8]) -> Result<(), Error> {
+        let actual: [u8; 32] = Sha256::digest(bytes).into();
+        if actual != self.sha256 {
+            return Err(Error::DigestMismatch {
+                expected: to_hex(&self.sha256),
+                actual: to_hex(&actual),
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn verify(&self, data: &[u8]) -> Result<(), Error> {
+        self.verify_len(data)?;
+        self.verify_digest(data)
+    }
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
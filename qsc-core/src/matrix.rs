@@ -0,0 +1,32 @@
+use qrcode::{Color, QrCode};
+
+// Read-only view of a square module matrix, shared by every renderer so they
+// accept both `QrCode` and hand-built symbols.
+pub trait Matrix {
+    fn width(&self) -> usize;
+    fn color(&self, x: usize, y: usize) -> Color;
+
+    fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.color(x, y) == Color::Dark
+    }
+}
+
+impl Matrix for QrCode {
+    fn width(&self) -> usize {
+        QrCode::width(self)
+    }
+
+    fn color(&self, x: usize, y: usize) -> Color {
+        self[(x, y)]
+    }
+}
+
+impl<M: Matrix + ?Sized> Matrix for Box<M> {
+    fn width(&self) -> usize {
+        (**self).width()
+    }
+
+    fn color(&self, x: usize, y: usize) -> Color {
+        (**self).color(x, y)
+    }
+}
@@ -0,0 +1,256 @@
+use crate::constants::{DEFAULT_PAPER_COLUMNS, DEFAULT_QUIET_ZONE, MAX_PAPER_COLUMNS};
+use crate::error::Error;
+use crate::matrix::Matrix;
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str};
+
+// Everything below is in PDF points (1/72 inch).
+const MARGIN: f32 = 36.0;
+const GAP: f32 = 18.0;
+const HEADER_HEIGHT: f32 = 28.0;
+const HEADER_FONT_SIZE: f32 = 10.0;
+const LABEL_FONT_SIZE: f32 = 8.0;
+const LABEL_LEADING: f32 = 10.0;
+const LABEL_LINES: usize = 3;
+// Rough Helvetica advance, only used to keep labels inside their cell.
+const AVERAGE_CHAR_WIDTH: f32 = 0.55;
+const FONT_NAME: Name = Name(b"F1");
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PageSize {
+    #[default]
+    A4,
+    Letter,
+}
+
+impl PageSize {
+    pub fn dimensions(self) -> (f32, f32) {
+        match self {
+            PageSize::A4 => (595.276, 841.89),
+            PageSize::Letter => (612.0, 792.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaperOptions {
+    pub page_size: PageSize,
+    pub columns: usize,
+    pub quiet_zone: u32,
+}
+
+impl Default for PaperOptions {
+    fn default() -> Self {
+        Self {
+            page_size: PageSize::default(),
+            columns: DEFAULT_PAPER_COLUMNS,
+            quiet_zone: DEFAULT_QUIET_ZONE,
+        }
+    }
+}
+
+// A code plus the checksum printed under it.
+pub struct PaperFrame<M> {
+    pub code: M,
+    pub checksum: u32,
+}
+
+struct Layout {
+    page_width: f32,
+    page_height: f32,
+    columns: usize,
+    rows: usize,
+    cell: f32,
+}
+
+impl Layout {
+    fn new(options: &PaperOptions) -> Self {
+        let (page_width, page_height) = options.page_size.dimensions();
+        let columns = options.columns.clamp(1, MAX_PAPER_COLUMNS);
+        let cell = (page_width - 2.0 * MARGIN - (columns - 1) as f32 * GAP) / columns as f32;
+        let row_height = cell + LABEL_LINES as f32 * LABEL_LEADING + GAP;
+        let usable = page_height - 2.0 * MARGIN - HEADER_HEIGHT + GAP;
+        let rows = ((usable / row_height) as usize).max(1);
+
+        Self {
+            page_width,
+            page_height,
+            columns,
+            rows,
+            cell,
+        }
+    }
+
+    fn per_page(&self) -> usize {
+        self.columns * self.rows
+    }
+
+    // Top-left corner of the cell, in PDF coordinates (origin bottom-left).
+    fn cell_origin(&self, slot: usize) -> (f32, f32) {
+        let row_height = self.cell + LABEL_LINES as f32 * LABEL_LEADING + GAP;
+        let x = MARGIN + (slot % self.columns) as f32 * (self.cell + GAP);
+        let y =
+            self.page_height - MARGIN - HEADER_HEIGHT - (slot / self.columns) as f32 * row_height;
+        (x, y)
+    }
+}
+
+// Text is written as raw bytes in the font's default encoding, which only
+// agrees with UTF-8 on printable ASCII; anything else becomes '?'.
+fn printable(text: &str, max_chars: usize) -> String {
+    let text: String = text
+        .chars()
+        .map(|c| {
+            if c.is_ascii_graphic() || c == ' ' {
+                c
+            } else {
+                '?'
+            }
+        })
+        .collect();
+    if text.chars().count() <= max_chars {
+        return text;
+    }
+    let kept: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+    format!("{}...", kept)
+}
+
+fn draw_text(content: &mut Content, x: f32, y: f32, size: f32, text: &str) {
+    content
+        .begin_text()
+        .set_font(FONT_NAME, size)
+        .next_line(x, y)
+        .show(Str(text.as_bytes()))
+        .end_text();
+}
+
+fn draw_code(
+    content: &mut Content,
+    code: &impl Matrix,
+    x: f32,
+    top: f32,
+    side: f32,
+    quiet_zone: u32,
+) {
+    let width = code.width();
+    let module = side / (width + 2 * quiet_zone as usize) as f32;
+    let x0 = x + quiet_zone as f32 * module;
+    let y0 = top - quiet_zone as f32 * module;
+
+    // Light cutting guide around the full symbol including its quiet zone.
+    content
+        .set_stroke_gray(0.8)
+        .set_line_width(0.5)
+        .rect(x, top - side, side, side)
+        .stroke();
+
+    content.set_fill_gray(0.0);
+    for row in 0..width {
+        let mut col = 0;
+        while col < width {
+            if !code.is_dark(col, row) {
+                col += 1;
+                continue;
+            }
+            let start = col;
+            while col < width && code.is_dark(col, row) {
+                col += 1;
+            }
+            content.rect(
+                x0 + start as f32 * module,
+                y0 - (row + 1) as f32 * module,
+                (col - start) as f32 * module,
+                module,
+            );
+        }
+    }
+    content.fill_nonzero();
+}
+
+// Paper backup: a grid of codes per page, each labelled with its position in
+// the sequence, the file name and the frame checksum.
+pub fn render_pdf<M: Matrix>(
+    file_name: &str,
+    frames: &[PaperFrame<M>],
+    options: &PaperOptions,
+) -> Result<Vec<u8>, Error> {
+    if frames.is_empty() {
+        return Err(Error::EmptySequence);
+    }
+
+    let layout = Layout::new(options);
+    let total = frames.len();
+    let page_count = total.div_ceil(layout.per_page());
+    let label_chars = (layout.cell / (LABEL_FONT_SIZE * AVERAGE_CHAR_WIDTH)) as usize;
+    let header_chars =
+        ((layout.page_width - 2.0 * MARGIN) / (HEADER_FONT_SIZE * AVERAGE_CHAR_WIDTH)) as usize;
+
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let font_id = Ref::new(3);
+    let page_ids: Vec<Ref> = (0..page_count)
+        .map(|i| Ref::new(4 + 2 * i as i32))
+        .collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().copied())
+        .count(page_count as i32);
+    pdf.type1_font(font_id).base_font(Name(b"Helvetica"));
+
+    for (page, chunk) in frames.chunks(layout.per_page()).enumerate() {
+        let page_id = page_ids[page];
+        let content_id = Ref::new(page_id.get() + 1);
+
+        let mut content = Content::new();
+        let header = format!(
+            "{} - {} frames - page {} / {}",
+            file_name,
+            total,
+            page + 1,
+            page_count
+        );
+        draw_text(
+            &mut content,
+            MARGIN,
+            layout.page_height - MARGIN - HEADER_FONT_SIZE,
+            HEADER_FONT_SIZE,
+            &printable(&header, header_chars),
+        );
+
+        for (slot, frame) in chunk.iter().enumerate() {
+            let index = page * layout.per_page() + slot;
+            let (x, top) = layout.cell_origin(slot);
+            draw_code(
+                &mut content,
+                &frame.code,
+                x,
+                top,
+                layout.cell,
+                options.quiet_zone,
+            );
+
+            let labels = [
+                format!("Frame {} / {}", index + 1, total),
+                printable(file_name, label_chars),
+                format!("CRC32 {:08x}", frame.checksum),
+            ];
+            for (line, label) in labels.iter().enumerate() {
+                let y = top - layout.cell - (line + 1) as f32 * LABEL_LEADING;
+                draw_text(&mut content, x, y, LABEL_FONT_SIZE, label);
+            }
+        }
+
+        let mut page_writer = pdf.page(page_id);
+        page_writer
+            .media_box(Rect::new(0.0, 0.0, layout.page_width, layout.page_height))
+            .parent(page_tree_id)
+            .contents(content_id);
+        page_writer.resources().fonts().pair(FONT_NAME, font_id);
+        page_writer.finish();
+
+        pdf.stream(content_id, &content.finish());
+    }
+
+    Ok(pdf.finish())
+}
@@ -0,0 +1,35 @@
+use crate::error::Error;
+use crate::matrix::Matrix;
+use crate::render::rasterize_luma;
+use png::{BitDepth, ColorType, Encoder};
+
+pub fn encode_png(pixels: &[u8], size: u32) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    let mut encoder = Encoder::new(&mut bytes, size, size);
+    encoder.set_color(ColorType::Grayscale);
+    encoder.set_depth(BitDepth::Eight);
+
+    let mut writer = encoder
+        .write_header()
+        .map_err(|e| Error::ImageEncoding(e.to_string()))?;
+    writer
+        .write_image_data(pixels)
+        .map_err(|e| Error::ImageEncoding(e.to_string()))?;
+    writer
+        .finish()
+        .map_err(|e| Error::ImageEncoding(e.to_string()))?;
+
+    Ok(bytes)
+}
+
+// Greyscale PNG with the same layout as the on-screen canvas.
+pub fn render_png(
+    matrix: &(impl Matrix + ?Sized),
+    canvas_size: u32,
+    quiet_zone: u32,
+) -> Result<Vec<u8>, Error> {
+    encode_png(
+        &rasterize_luma(matrix, canvas_size, quiet_zone),
+        canvas_size,
+    )
+}
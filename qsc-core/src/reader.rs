@@ -0,0 +1,450 @@
+use crate::error::Error;
+use crate::matrix::Matrix;
+use crate::reed_solomon::correct;
+use qrcode::canvas::is_functional;
+use qrcode::{EcLevel, Version};
+
+const FORMAT_INFO_MASK: u32 = 0x5412;
+const FORMAT_INFO_GENERATOR: u32 = 0x537;
+const VERSION_INFO_GENERATOR: u32 = 0x1f25;
+const MAX_INFO_BIT_ERRORS: u32 = 3;
+const ALPHANUMERIC_CHARSET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+// Error correction codewords per block and block count for versions 1 to 40,
+// indexed by L, M, Q, H (ISO/IEC 18004, table 9).
+static BLOCK_LAYOUT: [[(usize, usize); 4]; 40] = [
+    [(7, 1), (10, 1), (13, 1), (17, 1)],
+    [(10, 1), (16, 1), (22, 1), (28, 1)],
+    [(15, 1), (26, 1), (18, 2), (22, 2)],
+    [(20, 1), (18, 2), (26, 2), (16, 4)],
+    [(26, 1), (24, 2), (18, 4), (22, 4)],
+    [(18, 2), (16, 4), (24, 4), (28, 4)],
+    [(20, 2), (18, 4), (18, 6), (26, 5)],
+    [(24, 2), (22, 4), (22, 6), (26, 6)],
+    [(30, 2), (22, 5), (20, 8), (24, 8)],
+    [(18, 4), (26, 5), (24, 8), (28, 8)],
+    [(20, 4), (30, 5), (28, 8), (24, 11)],
+    [(24, 4), (22, 8), (26, 10), (28, 11)],
+    [(26, 4), (22, 9), (24, 12), (22, 16)],
+    [(30, 4), (24, 9), (20, 16), (24, 16)],
+    [(22, 6), (24, 10), (30, 12), (24, 18)],
+    [(24, 6), (28, 10), (24, 17), (30, 16)],
+    [(28, 6), (28, 11), (28, 16), (28, 19)],
+    [(30, 6), (26, 13), (28, 18), (28, 21)],
+    [(28, 7), (26, 14), (26, 21), (26, 25)],
+    [(28, 8), (26, 16), (30, 20), (28, 25)],
+    [(28, 8), (26, 17), (28, 23), (30, 25)],
+    [(28, 9), (28, 17), (30, 23), (24, 34)],
+    [(30, 9), (28, 18), (30, 25), (30, 30)],
+    [(30, 10), (28, 20), (30, 27), (30, 32)],
+    [(26, 12), (28, 21), (30, 29), (30, 35)],
+    [(28, 12), (28, 23), (28, 34), (30, 37)],
+    [(30, 12), (28, 25), (30, 34), (30, 40)],
+    [(30, 13), (28, 26), (30, 35), (30, 42)],
+    [(30, 14), (28, 28), (30, 38), (30, 45)],
+    [(30, 15), (28, 29), (30, 40), (30, 48)],
+    [(30, 16), (28, 31), (30, 43), (30, 51)],
+    [(30, 17), (28, 33), (30, 45), (30, 54)],
+    [(30, 18), (28, 35), (30, 48), (30, 57)],
+    [(30, 19), (28, 37), (30, 51), (30, 60)],
+    [(30, 19), (28, 38), (30, 53), (30, 63)],
+    [(30, 20), (28, 40), (30, 56), (30, 66)],
+    [(30, 21), (28, 43), (30, 59), (30, 70)],
+    [(30, 22), (28, 45), (30, 62), (30, 74)],
+    [(30, 24), (28, 47), (30, 65), (30, 77)],
+    [(30, 25), (28, 49), (30, 68), (30, 81)],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StructuredAppendHeader {
+    pub index: u8,
+    pub total: u8,
+    pub parity: u8,
+}
+
+// The content of one QR symbol read back from a module grid or an image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScannedCode {
+    pub version: Version,
+    pub ec_level: EcLevel,
+    pub data: Vec<u8>,
+    pub structured_append: Option<StructuredAppendHeader>,
+}
+
+fn decode_error(reason: &str) -> Error {
+    Error::QrDecode(reason.to_string())
+}
+
+// Reads a symbol from its module matrix, quiet zone excluded; up to the
+// error correction capacity of damaged modules are corrected.
+pub fn decode_symbol(matrix: &(impl Matrix + ?Sized)) -> Result<ScannedCode, Error> {
+    let width = matrix.width();
+    let modules: Vec<bool> = (0..width * width)
+        .map(|i| matrix.color(i % width, i / width).select(true, false))
+        .collect();
+    decode_modules(&modules, width)
+}
+
+pub(crate) fn decode_modules(modules: &[bool], width: usize) -> Result<ScannedCode, Error> {
+    if !(21..=177).contains(&width) || width % 4 != 1 {
+        return Err(decode_error("grid size is not a QR version"));
+    }
+    let version = ((width - 17) / 4) as i16;
+    let dark = |x: usize, y: usize| modules[y * width + x];
+
+    let (ec_level, mask) = read_format_info(&dark, width)?;
+    if version >= 7 && read_version_info(&dark, width) != Some(version) {
+        return Err(decode_error(
+            "version information does not match the grid size",
+        ));
+    }
+
+    let codewords = read_codewords(&dark, version, width, mask);
+    let data = correct_blocks(codewords, version, ec_level)?;
+    let (data, structured_append) = parse_segments(&data, version)?;
+
+    Ok(ScannedCode {
+        version: Version::Normal(version),
+        ec_level,
+        data,
+        structured_append,
+    })
+}
+
+fn ec_level_index(ec_level: EcLevel) -> usize {
+    match ec_level {
+        EcLevel::L => 0,
+        EcLevel::M => 1,
+        EcLevel::Q => 2,
+        EcLevel::H => 3,
+    }
+}
+
+fn format_bits(ec_level: EcLevel, mask: u32) -> u32 {
+    let level = match ec_level {
+        EcLevel::L => 1,
+        EcLevel::M => 0,
+        EcLevel::Q => 3,
+        EcLevel::H => 2,
+    };
+    let data = (level << 3) | mask;
+    let remainder = (0..10).fold(data, |rem, _| {
+        (rem << 1) ^ ((rem >> 9) * FORMAT_INFO_GENERATOR)
+    });
+    ((data << 10) | (remainder & 0x3ff)) ^ FORMAT_INFO_MASK
+}
+
+fn version_bits(version: i16) -> u32 {
+    let data = version as u32;
+    let remainder = (0..12).fold(data, |rem, _| {
+        (rem << 1) ^ ((rem >> 11) * VERSION_INFO_GENERATOR)
+    });
+    (data << 12) | (remainder & 0xfff)
+}
+
+fn read_bits(
+    positions: impl Iterator<Item = (usize, usize)>,
+    dark: impl Fn(usize, usize) -> bool,
+) -> u32 {
+    positions
+        .enumerate()
+        .fold(0, |bits, (i, (x, y))| bits | (dark(x, y) as u32) << i)
+}
+
+// Both copies are tried; the closest valid codeword within three bit errors wins.
+fn read_format_info(
+    dark: &impl Fn(usize, usize) -> bool,
+    width: usize,
+) -> Result<(EcLevel, u32), Error> {
+    let main = read_bits(
+        (0..6)
+            .map(|i| (8, i))
+            .chain([(8, 7), (8, 8), (7, 8)])
+            .chain((9..15).map(|i| (14 - i, 8))),
+        dark,
+    );
+    let side = read_bits(
+        (0..8)
+            .map(|i| (width - 1 - i, 8))
+            .chain((8..15).map(|i| (8, width - 15 + i))),
+        dark,
+    );
+
+    [EcLevel::L, EcLevel::M, EcLevel::Q, EcLevel::H]
+        .into_iter()
+        .flat_map(|ec_level| (0..8).map(move |mask| (ec_level, mask)))
+        .map(|(ec_level, mask)| {
+            let expected = format_bits(ec_level, mask);
+            let distance = (main ^ expected)
+                .count_ones()
+                .min((side ^ expected).count_ones());
+            (distance, ec_level, mask)
+        })
+        .min_by_key(|(distance, _, _)| *distance)
+        .filter(|(distance, _, _)| *distance <= MAX_INFO_BIT_ERRORS)
+        .map(|(_, ec_level, mask)| (ec_level, mask))
+        .ok_or_else(|| decode_error("unreadable format information"))
+}
+
+fn read_version_info(dark: &impl Fn(usize, usize) -> bool, width: usize) -> Option<i16> {
+    let top_right = read_bits((0..18).map(|i| (width - 11 + i % 3, i / 3)), dark);
+    let bottom_left = read_bits((0..18).map(|i| (i / 3, width - 11 + i % 3)), dark);
+
+    (7..=40)
+        .map(|version| {
+            let expected = version_bits(version);
+            let distance = (top_right ^ expected)
+                .count_ones()
+                .min((bottom_left ^ expected).count_ones());
+            (distance, version)
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .filter(|(distance, _)| *distance <= MAX_INFO_BIT_ERRORS)
+        .map(|(_, version)| version)
+}
+
+// The version read from a sampled grid, used to correct a misjudged size.
+pub(crate) fn sampled_version(modules: &[bool], width: usize) -> Option<i16> {
+    read_version_info(&|x: usize, y: usize| modules[y * width + x], width)
+}
+
+fn is_masked(mask: u32, x: usize, y: usize) -> bool {
+    match mask {
+        0 => (x + y).is_multiple_of(2),
+        1 => y.is_multiple_of(2),
+        2 => x.is_multiple_of(3),
+        3 => (x + y).is_multiple_of(3),
+        4 => (x / 3 + y / 2).is_multiple_of(2),
+        5 => (x * y) % 2 + (x * y) % 3 == 0,
+        6 => ((x * y) % 2 + (x * y) % 3).is_multiple_of(2),
+        _ => ((x + y) % 2 + (x * y) % 3).is_multiple_of(2),
+    }
+}
+
+fn is_function_module(version: i16, width: usize, x: usize, y: usize) -> bool {
+    let in_version_info = version >= 7
+        && ((x >= width - 11 && x < width - 8 && y < 6)
+            || (y >= width - 11 && y < width - 8 && x < 6));
+    in_version_info || is_functional(Version::Normal(version), width as i16, x as i16, y as i16)
+}
+
+// Walks the two-module columns from the bottom right in the zigzag order of
+// ISO/IEC 18004 §7.7.3, skipping the vertical timing pattern.
+fn read_codewords(
+    dark: &impl Fn(usize, usize) -> bool,
+    version: i16,
+    width: usize,
+    mask: u32,
+) -> Vec<u8> {
+    let mut codewords = Vec::with_capacity(width * width / 8);
+    let mut current = 0u8;
+    let mut bits = 0;
+
+    let mut right = width - 1;
+    while right >= 1 {
+        if right == 6 {
+            right = 5;
+        }
+        let upward = (right + 1) & 2 == 0;
+        for vertical in 0..width {
+            let y = if upward {
+                width - 1 - vertical
+            } else {
+                vertical
+            };
+            for x in [right, right - 1] {
+                if is_function_module(version, width, x, y) {
+                    continue;
+                }
+                current = (current << 1) | (dark(x, y) ^ is_masked(mask, x, y)) as u8;
+                bits += 1;
+                if bits == 8 {
+                    codewords.push(current);
+                    current = 0;
+                    bits = 0;
+                }
+            }
+        }
+        if right < 2 {
+            break;
+        }
+        right -= 2;
+    }
+    codewords
+}
+
+// Splits the interleaved codewords into blocks, corrects each one and returns
+// the data codewords in order.
+fn correct_blocks(codewords: Vec<u8>, version: i16, ec_level: EcLevel) -> Result<Vec<u8>, Error> {
+    let (ec_len, block_count) = BLOCK_LAYOUT[version as usize - 1][ec_level_index(ec_level)];
+    let total = raw_codeword_count(version);
+    if codewords.len() < total {
+        return Err(decode_error("grid holds fewer codewords than its version"));
+    }
+
+    let short_blocks = block_count - total % block_count;
+    let short_data_len = total / block_count - ec_len;
+    let data_len = |block: usize| short_data_len + (block >= short_blocks) as usize;
+
+    let mut blocks: Vec<Vec<u8>> = (0..block_count)
+        .map(|block| Vec::with_capacity(data_len(block) + ec_len))
+        .collect();
+    let mut source = codewords.into_iter();
+    for i in 0..=short_data_len {
+        for (index, block) in blocks.iter_mut().enumerate() {
+            if i < data_len(index) {
+                block.extend(source.next());
+            }
+        }
+    }
+    for _ in 0..ec_len {
+        for block in blocks.iter_mut() {
+            block.extend(source.next());
+        }
+    }
+
+    let mut data = Vec::with_capacity(total - ec_len * block_count);
+    for (index, mut block) in blocks.into_iter().enumerate() {
+        correct(&mut block, ec_len).ok_or_else(|| decode_error("too many damaged codewords"))?;
+        data.extend_from_slice(&block[..data_len(index)]);
+    }
+    Ok(data)
+}
+
+fn raw_codeword_count(version: i16) -> usize {
+    let version = version as usize;
+    let mut modules = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let alignment = version / 7 + 2;
+        modules -= (25 * alignment - 10) * alignment - 55;
+        if version >= 7 {
+            modules -= 36;
+        }
+    }
+    modules / 8
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+    fn remaining(&self) -> usize {
+        self.bytes.len() * 8 - self.position
+    }
+
+    fn read(&mut self, bits: usize) -> Result<u32, Error> {
+        if bits > self.remaining() {
+            return Err(decode_error("segment runs past the end of the data"));
+        }
+        let value = (self.position..self.position + bits).fold(0, |value, i| {
+            (value << 1) | ((self.bytes[i / 8] >> (7 - i % 8)) & 1) as u32
+        });
+        self.position += bits;
+        Ok(value)
+    }
+}
+
+fn count_bits(version: i16, small: usize, medium: usize, large: usize) -> usize {
+    match version {
+        1..=9 => small,
+        10..=26 => medium,
+        _ => large,
+    }
+}
+
+// Concatenates every segment into raw bytes; numeric and alphanumeric
+// segments become their ASCII text and Kanji its Shift JIS bytes.
+fn parse_segments(
+    data: &[u8],
+    version: i16,
+) -> Result<(Vec<u8>, Option<StructuredAppendHeader>), Error> {
+    let mut reader = BitReader {
+        bytes: data,
+        position: 0,
+    };
+    let mut output = Vec::new();
+    let mut structured_append = None;
+
+    while reader.remaining() >= 4 {
+        match reader.read(4)? {
+            0b0000 => break,
+            0b0001 => {
+                let mut count = reader.read(count_bits(version, 10, 12, 14))? as usize;
+                while count > 0 {
+                    let digits = count.min(3);
+                    let value = reader.read([4, 7, 10][digits - 1])?;
+                    let text = format!("{:0width$}", value, width = digits);
+                    if text.len() != digits {
+                        return Err(decode_error("numeric group out of range"));
+                    }
+                    output.extend_from_slice(text.as_bytes());
+                    count -= digits;
+                }
+            }
+            0b0010 => {
+                let mut count = reader.read(count_bits(version, 9, 11, 13))? as usize;
+                while count > 0 {
+                    let pair = count >= 2;
+                    let value = reader.read(if pair { 11 } else { 6 })? as usize;
+                    let indices = if pair {
+                        vec![value / 45, value % 45]
+                    } else {
+                        vec![value]
+                    };
+                    for index in &indices {
+                        let byte = ALPHANUMERIC_CHARSET
+                            .get(*index)
+                            .ok_or_else(|| decode_error("alphanumeric value out of range"))?;
+                        output.push(*byte);
+                    }
+                    count -= indices.len();
+                }
+            }
+            0b0011 => {
+                let index = reader.read(4)? as u8;
+                let total = reader.read(4)? as u8 + 1;
+                let parity = reader.read(8)? as u8;
+                structured_append = Some(StructuredAppendHeader {
+                    index,
+                    total,
+                    parity,
+                });
+            }
+            0b0100 => {
+                let count = reader.read(count_bits(version, 8, 16, 16))?;
+                for _ in 0..count {
+                    output.push(reader.read(8)? as u8);
+                }
+            }
+            0b0101 => {}
+            0b0111 => {
+                let first = reader.read(8)?;
+                if first & 0x80 != 0 {
+                    let extra = if first & 0x40 == 0 { 8 } else { 16 };
+                    reader.read(extra)?;
+                }
+            }
+            0b1000 => {
+                let count = reader.read(count_bits(version, 8, 10, 12))?;
+                for _ in 0..count {
+                    let value = reader.read(13)?;
+                    let assembled = ((value / 0xc0) << 8) | (value % 0xc0);
+                    let shift_jis = if assembled < 0x1f00 {
+                        assembled + 0x8140
+                    } else {
+                        assembled + 0xc140
+                    };
+                    output.extend_from_slice(&(shift_jis as u16).to_be_bytes());
+                }
+            }
+            0b1001 => {
+                reader.read(8)?;
+            }
+            _ => return Err(decode_error("unknown segment mode")),
+        }
+    }
+
+    Ok((output, structured_append))
+}
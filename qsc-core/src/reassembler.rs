@@ -0,0 +1,250 @@
+use crate::compression::decompress;
+use crate::constants::{FLAG_DEFLATE, FLAG_ENCRYPTED, MAX_SEQUENCE_FRAMES};
+use crate::crypto::{associated_data, decrypt};
+use crate::error::Error;
+use crate::fountain::FountainDecoder;
+use crate::frame::{FrameHeader, decode_frame};
+use crate::manifest::Manifest;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushOutcome {
+    Accepted,
+    Duplicate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub received: usize,
+    pub total: Option<usize>,
+    pub manifest: bool,
+}
+
+impl Progress {
+    pub fn is_complete(&self) -> bool {
+        self.manifest && self.total == Some(self.received)
+    }
+}
+
+#[derive(Default)]
+pub struct Reassembler {
+    session_id: Option<u32>,
+    chunks: Vec<Option<Vec<u8>>>,
+    received: usize,
+    fountain: Option<FountainDecoder>,
+    manifest: Option<Manifest>,
+    // Fixed by the first accepted frame; every frame of a sequence agrees.
+    compression_flags: Option<u8>,
+    passphrase: Option<String>,
+}
+
+impl Reassembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_passphrase(mut self, passphrase: impl Into<String>) -> Self {
+        self.passphrase = Some(passphrase.into());
+        self
+    }
+
+    pub fn session_id(&self) -> Option<u32> {
+        self.session_id
+    }
+
+    pub fn manifest(&self) -> Option<&Manifest> {
+        self.manifest.as_ref()
+    }
+
+    pub fn push(&mut self, frame: &[u8]) -> Result<PushOutcome, Error> {
+        let (header, payload) = decode_frame(frame)?;
+
+        if let Some(session_id) = self.session_id
+            && session_id != header.session_id
+        {
+            return Err(Error::SessionMismatch {
+                expected: session_id,
+                actual: header.session_id,
+            });
+        }
+
+        let compression_flags = header.flags & FLAG_DEFLATE;
+        if self
+            .compression_flags
+            .is_some_and(|flags| flags != compression_flags)
+        {
+            return Err(Error::CompressionMismatch);
+        }
+
+        let outcome = if header.is_manifest() {
+            self.push_manifest(&header, payload)
+        } else if header.is_fountain() {
+            self.push_droplet(&header, payload)
+        } else {
+            self.push_chunk(&header, payload)
+        }?;
+        self.compression_flags = Some(compression_flags);
+        Ok(outcome)
+    }
+
+    fn push_manifest(
+        &mut self,
+        header: &FrameHeader,
+        payload: &[u8],
+    ) -> Result<PushOutcome, Error> {
+        if self.manifest.is_some() {
+            return Ok(PushOutcome::Duplicate);
+        }
+
+        self.manifest = Some(Manifest::from_bytes(payload)?);
+        self.session_id = Some(header.session_id);
+        Ok(PushOutcome::Accepted)
+    }
+
+    fn push_droplet(&mut self, header: &FrameHeader, payload: &[u8]) -> Result<PushOutcome, Error> {
+        if !self.chunks.is_empty() {
+            return Err(Error::ModeMismatch);
+        }
+
+        if self.fountain.is_none() {
+            // Droplets carry whole blocks, so the first one fixes the block size.
+            let data_len = header.total as usize;
+            if payload.is_empty() {
+                return Err(Error::InvalidHeader("empty fountain droplet".to_string()));
+            }
+            if data_len.div_ceil(payload.len()) > MAX_SEQUENCE_FRAMES {
+                return Err(Error::InvalidHeader(format!(
+                    "{} bytes in blocks of {} need more than {} droplets",
+                    data_len,
+                    payload.len(),
+                    MAX_SEQUENCE_FRAMES
+                )));
+            }
+        }
+        let fountain = self.fountain.get_or_insert_with(|| {
+            FountainDecoder::new(header.session_id, header.total as usize, payload.len())
+        });
+        self.session_id = Some(header.session_id);
+
+        if fountain.data_len() != header.total as usize {
+            return Err(Error::TotalMismatch {
+                expected: fountain.data_len() as u32,
+                actual: header.total,
+            });
+        }
+
+        if fountain.push(header.index, payload)? {
+            Ok(PushOutcome::Accepted)
+        } else {
+            Ok(PushOutcome::Duplicate)
+        }
+    }
+
+    fn push_chunk(&mut self, header: &FrameHeader, payload: &[u8]) -> Result<PushOutcome, Error> {
+        if self.fountain.is_some() {
+            return Err(Error::ModeMismatch);
+        }
+
+        let total = header.total as usize;
+        let index = header.index as usize;
+
+        if total == 0 || total > MAX_SEQUENCE_FRAMES {
+            return Err(Error::InvalidHeader(format!(
+                "{} frames announced, at most {} are supported",
+                total, MAX_SEQUENCE_FRAMES
+            )));
+        }
+
+        if self.chunks.is_empty() {
+            self.session_id = Some(header.session_id);
+            self.chunks = vec![None; total];
+        } else if self.chunks.len() != total {
+            return Err(Error::TotalMismatch {
+                expected: self.chunks.len() as u32,
+                actual: header.total,
+            });
+        }
+
+        let slot = self
+            .chunks
+            .get_mut(index)
+            .ok_or(Error::FrameIndexOutOfRange {
+                index: header.index,
+                total: header.total,
+            })?;
+
+        if slot.is_some() {
+            return Ok(PushOutcome::Duplicate);
+        }
+
+        *slot = Some(payload.to_vec());
+        self.received += 1;
+        Ok(PushOutcome::Accepted)
+    }
+
+    pub fn progress(&self) -> Progress {
+        if let Some(fountain) = &self.fountain {
+            return Progress {
+                received: fountain.solved(),
+                total: Some(fountain.block_count()),
+                manifest: self.manifest.is_some(),
+            };
+        }
+
+        Progress {
+            received: self.received,
+            total: (!self.chunks.is_empty()).then_some(self.chunks.len()),
+            manifest: self.manifest.is_some(),
+        }
+    }
+
+    // Source block indices in fountain mode.
+    pub fn missing_indices(&self) -> Vec<u32> {
+        if let Some(fountain) = &self.fountain {
+            return fountain.missing_blocks();
+        }
+
+        self.chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, chunk)| chunk.is_none())
+            .map(|(index, _)| index as u32)
+            .collect()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.progress().is_complete()
+    }
+
+    pub fn finish(self) -> Result<Vec<u8>, Error> {
+        let stream = match self.fountain {
+            Some(fountain) => fountain.finish()?,
+            None => {
+                let missing = self.missing_indices().len();
+                if self.chunks.is_empty() || missing > 0 {
+                    return Err(Error::Incomplete { missing });
+                }
+                self.chunks.into_iter().flatten().flatten().collect()
+            }
+        };
+
+        let manifest = self.manifest.ok_or(Error::MissingManifest)?;
+        let compression_flags = self.compression_flags.unwrap_or_default();
+
+        let Some(params) = &manifest.encryption else {
+            let data = decompress(stream, compression_flags, manifest.file_len as usize)?;
+            manifest.verify(&data)?;
+            return Ok(data);
+        };
+
+        manifest.verify_digest(&stream)?;
+        let passphrase = self
+            .passphrase
+            .as_deref()
+            .ok_or(Error::PassphraseRequired)?;
+        let aad = associated_data(compression_flags | FLAG_ENCRYPTED, manifest.file_len);
+        let plaintext = decrypt(&stream, &aad, passphrase, params)?;
+        let data = decompress(plaintext, compression_flags, manifest.file_len as usize)?;
+        manifest.verify_len(&data)?;
+        Ok(data)
+    }
+}
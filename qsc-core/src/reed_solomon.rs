@@ -0,0 +1,171 @@
+// Reed–Solomon error correction over GF(256) with the QR code polynomial
+// x^8 + x^4 + x^3 + x^2 + 1 and generator roots starting at a^0.
+const PRIMITIVE: u16 = 0x11d;
+
+struct Field {
+    exp: [u8; 512],
+    log: [u8; 256],
+}
+
+impl Field {
+    const fn new() -> Self {
+        let mut exp = [0u8; 512];
+        let mut log = [0u8; 256];
+        let mut value: u16 = 1;
+        let mut i = 0;
+        while i < 255 {
+            exp[i] = value as u8;
+            log[value as usize] = i as u8;
+            value <<= 1;
+            if value & 0x100 != 0 {
+                value ^= PRIMITIVE;
+            }
+            i += 1;
+        }
+        while i < 512 {
+            exp[i] = exp[i - 255];
+            i += 1;
+        }
+        Self { exp, log }
+    }
+
+    fn mul(&self, a: u8, b: u8) -> u8 {
+        if a == 0 || b == 0 {
+            return 0;
+        }
+        self.exp[self.log[a as usize] as usize + self.log[b as usize] as usize]
+    }
+
+    fn div(&self, a: u8, b: u8) -> u8 {
+        if a == 0 {
+            return 0;
+        }
+        self.exp[self.log[a as usize] as usize + 255 - self.log[b as usize] as usize]
+    }
+
+    fn pow(&self, power: usize) -> u8 {
+        self.exp[power % 255]
+    }
+
+    // Polynomials are stored lowest degree first.
+    fn eval(&self, poly: &[u8], x: u8) -> u8 {
+        poly.iter()
+            .rev()
+            .fold(0, |acc, coefficient| self.mul(acc, x) ^ coefficient)
+    }
+}
+
+static FIELD: Field = Field::new();
+
+// Corrects `codeword` (data followed by `ec_len` check bytes) in place and
+// returns the number of bytes fixed, or `None` when there are too many errors.
+pub(crate) fn correct(codeword: &mut [u8], ec_len: usize) -> Option<usize> {
+    let field = &FIELD;
+    let n = codeword.len();
+    let syndromes = syndromes(codeword, ec_len);
+    if syndromes.iter().all(|s| *s == 0) {
+        return Some(0);
+    }
+
+    let locator = error_locator(&syndromes);
+    let errors = locator.len() - 1;
+    if errors * 2 > ec_len {
+        return None;
+    }
+
+    // Byte `i` of the codeword is the coefficient of x^(n - 1 - i).
+    let positions: Vec<usize> = (0..n)
+        .filter(|power| field.eval(&locator, field.pow(255 - power % 255)) == 0)
+        .collect();
+    if positions.len() != errors {
+        return None;
+    }
+
+    let mut evaluator = vec![0u8; ec_len];
+    for (i, s) in syndromes.iter().enumerate() {
+        for (j, l) in locator.iter().enumerate().take(ec_len - i) {
+            evaluator[i + j] ^= field.mul(*s, *l);
+        }
+    }
+    let derivative: Vec<u8> = locator
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, l)| if i % 2 == 1 { *l } else { 0 })
+        .collect();
+
+    for power in positions {
+        let x = field.pow(power);
+        let x_inverse = field.pow(255 - power % 255);
+        let denominator = field.eval(&derivative, x_inverse);
+        if denominator == 0 {
+            return None;
+        }
+        let magnitude = field.mul(x, field.div(field.eval(&evaluator, x_inverse), denominator));
+        codeword[n - 1 - power] ^= magnitude;
+    }
+
+    syndromes_are_zero(codeword, ec_len).then_some(errors)
+}
+
+fn syndromes(codeword: &[u8], ec_len: usize) -> Vec<u8> {
+    let field = &FIELD;
+    (0..ec_len)
+        .map(|i| {
+            let x = field.pow(i);
+            codeword
+                .iter()
+                .fold(0, |acc, byte| field.mul(acc, x) ^ byte)
+        })
+        .collect()
+}
+
+fn syndromes_are_zero(codeword: &[u8], ec_len: usize) -> bool {
+    syndromes(codeword, ec_len).iter().all(|s| *s == 0)
+}
+
+// Berlekamp–Massey; returns the error locator lowest degree first.
+fn error_locator(syndromes: &[u8]) -> Vec<u8> {
+    let field = &FIELD;
+    let mut current = vec![1u8];
+    let mut previous = vec![1u8];
+    let mut degree = 0;
+    let mut shift = 1;
+    let mut last_discrepancy = 1u8;
+
+    for n in 0..syndromes.len() {
+        let discrepancy = (1..=degree)
+            .filter(|i| *i < current.len())
+            .fold(syndromes[n], |acc, i| {
+                acc ^ field.mul(current[i], syndromes[n - i])
+            });
+
+        if discrepancy == 0 {
+            shift += 1;
+            continue;
+        }
+
+        let scale = field.div(discrepancy, last_discrepancy);
+        let mut next = current.clone();
+        if next.len() < previous.len() + shift {
+            next.resize(previous.len() + shift, 0);
+        }
+        for (i, coefficient) in previous.iter().enumerate() {
+            next[i + shift] ^= field.mul(scale, *coefficient);
+        }
+
+        if 2 * degree <= n {
+            degree = n + 1 - degree;
+            previous = current;
+            last_discrepancy = discrepancy;
+            shift = 1;
+        } else {
+            shift += 1;
+        }
+        current = next;
+    }
+
+    current.truncate(degree + 1);
+    current.resize(degree + 1, 0);
+    current
+}
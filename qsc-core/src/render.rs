@@ -0,0 +1,115 @@
+use crate::constants::{MAX_QR_CODE_VERSION, MIN_MODULE_PIXELS, MIN_QR_CODE_VERSION};
+use crate::error::Error;
+use crate::matrix::Matrix;
+use qrcode::Version;
+
+pub fn validate_version(version: Version) -> Result<(), Error> {
+    match version {
+        Version::Normal(v) if (MIN_QR_CODE_VERSION..=MAX_QR_CODE_VERSION).contains(&v) => Ok(()),
+        _ => Err(Error::UnsupportedVersion(version)),
+    }
+}
+
+pub fn min_canvas_size(version: Version, quiet_zone: u32) -> Result<u32, Error> {
+    quiet_zone
+        .checked_mul(2)
+        .and_then(|margin| margin.checked_add(version.width() as u32))
+        .and_then(|modules| modules.checked_mul(MIN_MODULE_PIXELS))
+        .ok_or(Error::QuietZoneTooLarge(quiet_zone))
+}
+
+// Below `MIN_MODULE_PIXELS` per module neighbouring modules blur together and
+// scanners cannot resolve the grid.
+pub fn check_canvas_size(version: Version, canvas_size: u32, quiet_zone: u32) -> Result<(), Error> {
+    validate_version(version)?;
+
+    let min_size = min_canvas_size(version, quiet_zone)?;
+    if canvas_size < min_size {
+        return Err(Error::CanvasTooSmall {
+            canvas_size,
+            min_size,
+        });
+    }
+
+    Ok(())
+}
+
+const DARK_RGBA: [u8; 4] = [0, 0, 0, 255];
+const LIGHT_RGBA: [u8; 4] = [255, 255, 255, 255];
+const DARK_LUMA: [u8; 1] = [0];
+const LIGHT_LUMA: [u8; 1] = [255];
+
+// Largest whole number of pixels per module that still leaves room for the
+// quiet zone, so module edges never land mid-pixel and smear. A border too
+// wide to count in a u32 leaves no room at all.
+pub fn module_pixels(width: usize, canvas_size: u32, quiet_zone: u32) -> u32 {
+    u32::try_from(width)
+        .ok()
+        .and_then(|width| width.checked_add(quiet_zone.checked_mul(2)?))
+        .map_or(0, |modules| canvas_size / modules.max(1))
+}
+
+// Offset of the symbol's top-left module when centred on the canvas; the
+// light margin around it is at least `quiet_zone` modules wide.
+pub fn symbol_offset(width: usize, canvas_size: u32, quiet_zone: u32) -> u32 {
+    let module = module_pixels(width, canvas_size, quiet_zone);
+    (canvas_size - width as u32 * module) / 2
+}
+
+fn rasterize(
+    matrix: &(impl Matrix + ?Sized),
+    canvas_size: u32,
+    quiet_zone: u32,
+    dark: &[u8],
+    light: &[u8],
+) -> Vec<u8> {
+    let size = canvas_size as usize;
+    let channels = dark.len();
+    let width = matrix.width();
+    let module = module_pixels(width, canvas_size, quiet_zone) as usize;
+    let offset = symbol_offset(width, canvas_size, quiet_zone) as usize;
+
+    let mut pixels = light.repeat(size * size);
+    if module == 0 {
+        return pixels;
+    }
+
+    for y in 0..width {
+        for x in 0..width {
+            if !matrix.is_dark(x, y) {
+                continue;
+            }
+            for py in offset + y * module..offset + (y + 1) * module {
+                let row = (py * size + offset + x * module) * channels;
+                for pixel in pixels[row..row + module * channels].chunks_exact_mut(channels) {
+                    pixel.copy_from_slice(dark);
+                }
+            }
+        }
+    }
+
+    pixels
+}
+
+// Square RGBA buffer of `canvas_size` pixels a side, ready for `ImageData`.
+pub fn rasterize_rgba(
+    matrix: &(impl Matrix + ?Sized),
+    canvas_size: u32,
+    quiet_zone: u32,
+) -> Vec<u8> {
+    rasterize(matrix, canvas_size, quiet_zone, &DARK_RGBA, &LIGHT_RGBA)
+}
+
+// Same layout as `rasterize_rgba`, one grey byte per pixel.
+pub fn rasterize_luma(
+    matrix: &(impl Matrix + ?Sized),
+    canvas_size: u32,
+    quiet_zone: u32,
+) -> Vec<u8> {
+    rasterize(matrix, canvas_size, quiet_zone, &DARK_LUMA, &LIGHT_LUMA)
+}
+
+// `index` is zero-based; names are one-based and zero-padded so they sort.
+pub fn frame_file_name(index: usize, total: usize, extension: &str) -> String {
+    format!("frame-{:04}-of-{:04}.{}", index + 1, total, extension)
+}
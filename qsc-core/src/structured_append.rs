@@ -0,0 +1,209 @@
+use crate::capacity::{MODE_INDICATOR_BITS, data_capacity_bits};
+use crate::error::Error;
+use crate::matrix::Matrix;
+use crate::reader::{ScannedCode, StructuredAppendHeader};
+use qrcode::canvas::Canvas;
+use qrcode::ec::construct_codewords;
+use qrcode::types::Mode;
+use qrcode::{Color, EcLevel, Version};
+
+pub const MAX_STRUCTURED_APPEND_SYMBOLS: usize = 16;
+
+// Mode indicator, symbol position, total and parity.
+const STRUCTURED_APPEND_HEADER_BITS: usize = 4 + 4 + 4 + 8;
+const PAD_CODEWORDS: [u8; 2] = [0xec, 0x11];
+
+// A QR symbol assembled from raw codewords, for modes the `qrcode` encoder
+// does not expose.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    version: Version,
+    ec_level: EcLevel,
+    width: usize,
+    colors: Vec<Color>,
+    data: Vec<u8>,
+}
+
+impl Symbol {
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    pub fn error_correction_level(&self) -> EcLevel {
+        self.ec_level
+    }
+
+    pub fn to_colors(&self) -> Vec<Color> {
+        self.colors.clone()
+    }
+
+    // The slice of the original data carried by this symbol.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl Matrix for Symbol {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn color(&self, x: usize, y: usize) -> Color {
+        self.colors[y * self.width + x]
+    }
+}
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, bits: usize, value: u32) {
+        for shift in (0..bits).rev() {
+            if self.len.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if (value >> shift) & 1 == 1 {
+                let last = self.bytes.len() - 1;
+                self.bytes[last] |= 0x80 >> (self.len % 8);
+            }
+            self.len += 1;
+        }
+    }
+
+    fn finish(mut self, capacity_bits: usize) -> Vec<u8> {
+        let terminator = (capacity_bits - self.len).min(4);
+        self.push(terminator, 0);
+        let padding = (8 - self.len % 8) % 8;
+        self.push(padding, 0);
+
+        let capacity_bytes = capacity_bits / 8;
+        let mut pad = PAD_CODEWORDS.iter().cycle();
+        while self.bytes.len() < capacity_bytes {
+            self.bytes.push(*pad.next().expect("cycled padding"));
+        }
+        self.bytes
+    }
+}
+
+pub fn structured_append_symbol_capacity(
+    version: Version,
+    ec_level: EcLevel,
+) -> Result<usize, Error> {
+    let capacity_bits = data_capacity_bits(version, ec_level)?;
+    let overhead =
+        STRUCTURED_APPEND_HEADER_BITS + MODE_INDICATOR_BITS + Mode::Byte.length_bits_count(version);
+    Ok(capacity_bits.saturating_sub(overhead) / 8)
+}
+
+// Splits `data` over up to 16 symbols carrying the standard Structured Append
+// header, so any reader that supports it can stitch the original bytes back.
+pub fn encode_structured_append(
+    data: &[u8],
+    version: Version,
+    ec_level: EcLevel,
+) -> Result<Vec<Symbol>, Error> {
+    let capacity_bits = data_capacity_bits(version, ec_level)?;
+    let symbol_capacity = structured_append_symbol_capacity(version, ec_level)?;
+    if symbol_capacity == 0 {
+        return Err(Error::CapacityTooSmall { capacity: 0 });
+    }
+
+    let total = data.len().div_ceil(symbol_capacity).max(1);
+    if total > MAX_STRUCTURED_APPEND_SYMBOLS {
+        return Err(Error::TooManySymbols {
+            needed: total,
+            max: MAX_STRUCTURED_APPEND_SYMBOLS,
+        });
+    }
+
+    let parity = data.iter().fold(0u8, |parity, byte| parity ^ byte);
+    let length_bits = Mode::Byte.length_bits_count(version);
+
+    (0..total)
+        .map(|index| {
+            let start = (index * symbol_capacity).min(data.len());
+            let end = ((index + 1) * symbol_capacity).min(data.len());
+            let chunk = &data[start..end];
+
+            let mut bits = BitWriter::new();
+            bits.push(MODE_INDICATOR_BITS, 0b0011);
+            bits.push(4, index as u32);
+            bits.push(4, (total - 1) as u32);
+            bits.push(8, parity as u32);
+            bits.push(MODE_INDICATOR_BITS, 0b0100);
+            bits.push(length_bits, chunk.len() as u32);
+            for &byte in chunk {
+                bits.push(8, byte as u32);
+            }
+
+            let codewords = bits.finish(capacity_bits);
+            let (encoded, ec) = construct_codewords(&codewords, version, ec_level)?;
+
+            let mut canvas = Canvas::new(version, ec_level);
+            canvas.draw_all_functional_patterns();
+            canvas.draw_data(&encoded, &ec);
+
+            Ok(Symbol {
+                version,
+                ec_level,
+                width: version.width() as usize,
+                colors: canvas.apply_best_mask().into_colors(),
+                data: chunk.to_vec(),
+            })
+        })
+        .collect()
+}
+
+// Stitches scanned symbols back together in position order; duplicates are
+// ignored and the parity byte must match the joined data.
+pub fn join_structured_append(codes: &[ScannedCode]) -> Result<Vec<u8>, Error> {
+    let symbols: Vec<(StructuredAppendHeader, &[u8])> = codes
+        .iter()
+        .filter_map(|code| {
+            code.structured_append
+                .map(|header| (header, &code.data[..]))
+        })
+        .collect();
+    let (first, _) = symbols.first().ok_or(Error::EmptySequence)?;
+
+    let mut parts: Vec<Option<&[u8]>> = vec![None; first.total as usize];
+    for (header, data) in &symbols {
+        if header.total != first.total {
+            return Err(Error::TotalMismatch {
+                expected: first.total as u32,
+                actual: header.total as u32,
+            });
+        }
+        let part = parts
+            .get_mut(header.index as usize)
+            .ok_or(Error::FrameIndexOutOfRange {
+                index: header.index as u32,
+                total: header.total as u32,
+            })?;
+        part.get_or_insert(data);
+    }
+
+    let missing = parts.iter().filter(|part| part.is_none()).count();
+    if missing > 0 {
+        return Err(Error::Incomplete { missing });
+    }
+
+    let data: Vec<u8> = parts.into_iter().flatten().flatten().copied().collect();
+    let parity = data.iter().fold(0u8, |parity, byte| parity ^ byte);
+    if parity != first.parity {
+        return Err(Error::ParityMismatch {
+            expected: first.parity,
+            actual: parity,
+        });
+    }
+    Ok(data)
+}
@@ -0,0 +1,101 @@
+use crate::matrix::Matrix;
+use std::fmt::Write;
+
+const DARK_FILL: &str = "#000";
+const LIGHT_FILL: &str = "#fff";
+
+// One subpath per horizontal run of dark modules, in module units.
+fn module_path(matrix: &(impl Matrix + ?Sized), x0: usize, y0: usize) -> String {
+    let width = matrix.width();
+    let mut path = String::new();
+
+    for y in 0..width {
+        let mut x = 0;
+        while x < width {
+            if !matrix.is_dark(x, y) {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < width && matrix.is_dark(x, y) {
+                x += 1;
+            }
+            let _ = write!(
+                path,
+                "M{} {}h{}v1h-{}z",
+                x0 + start,
+                y0 + y,
+                x - start,
+                x - start
+            );
+        }
+    }
+
+    path
+}
+
+fn svg_document(view_width: usize, view_height: usize, module_size: u32, body: &str) -> String {
+    format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            "\n",
+            r#"<svg xmlns="http://www.w3.org/2000/svg" version="1.1" width="{}" height="{}" viewBox="0 0 {} {}" shape-rendering="crispEdges">"#,
+            r#"<rect width="{}" height="{}" fill="{}"/>"#,
+            "{}</svg>\n"
+        ),
+        view_width as u32 * module_size,
+        view_height as u32 * module_size,
+        view_width,
+        view_height,
+        view_width,
+        view_height,
+        LIGHT_FILL,
+        body
+    )
+}
+
+// Vector rendering straight from the module matrix, scalable without blur.
+pub fn render_svg(matrix: &(impl Matrix + ?Sized), module_size: u32, quiet_zone: u32) -> String {
+    let quiet_zone = quiet_zone as usize;
+    let size = matrix.width() + 2 * quiet_zone;
+    let body = format!(
+        r#"<path fill="{}" d="{}"/>"#,
+        DARK_FILL,
+        module_path(matrix, quiet_zone, quiet_zone)
+    );
+
+    svg_document(size, size, module_size, &body)
+}
+
+// Every frame of a sequence on one sheet, laid out left to right in a grid
+// of `columns`; each cell keeps its own quiet zone.
+pub fn render_svg_sheet<M: Matrix>(
+    matrices: &[M],
+    module_size: u32,
+    quiet_zone: u32,
+    columns: usize,
+) -> String {
+    let quiet_zone = quiet_zone as usize;
+    let columns = columns.clamp(1, matrices.len().max(1));
+    let rows = matrices.len().div_ceil(columns).max(1);
+    let cell = matrices
+        .iter()
+        .map(|matrix| matrix.width())
+        .max()
+        .unwrap_or(0)
+        + 2 * quiet_zone;
+
+    let mut body = String::new();
+    for (index, matrix) in matrices.iter().enumerate() {
+        let x0 = (index % columns) * cell + quiet_zone;
+        let y0 = (index / columns) * cell + quiet_zone;
+        let _ = write!(
+            body,
+            r#"<path fill="{}" d="{}"/>"#,
+            DARK_FILL,
+            module_path(matrix, x0, y0)
+        );
+    }
+
+    svg_document(columns * cell, rows * cell, module_size, &body)
+}
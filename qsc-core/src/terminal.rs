@@ -0,0 +1,138 @@
+use crate::constants::DEFAULT_QUIET_ZONE;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::encoder::Encoder;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::frame::frame_caption;
+use crate::matrix::Matrix;
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_CURSOR_HOME: &str = "\x1b[H";
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J";
+// Foreground and background codes for black and bright white.
+const ANSI_DARK: (u8, u8) = (30, 40);
+const ANSI_LIGHT: (u8, u8) = (97, 107);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalOptions {
+    pub quiet_zone: u32,
+    // Paint explicit black and white cells instead of relying on the
+    // terminal's own colours.
+    pub colors: bool,
+    // Without colours the glyphs are drawn in the terminal's text colour, so
+    // on a dark background they must stand for the light modules.
+    pub dark_background: bool,
+}
+
+impl Default for TerminalOptions {
+    fn default() -> Self {
+        Self {
+            quiet_zone: DEFAULT_QUIET_ZONE,
+            colors: false,
+            dark_background: true,
+        }
+    }
+}
+
+// Two module rows per text line using the upper/lower half-block glyphs.
+pub fn render_terminal(matrix: &(impl Matrix + ?Sized), options: &TerminalOptions) -> String {
+    let width = matrix.width() as isize;
+    let quiet_zone = options.quiet_zone as isize;
+    let size = width + 2 * quiet_zone;
+    let is_dark = |x: isize, y: isize| {
+        let (x, y) = (x - quiet_zone, y - quiet_zone);
+        (0..width).contains(&x) && (0..width).contains(&y) && matrix.is_dark(x as usize, y as usize)
+    };
+
+    let mut out = String::new();
+    for y in (0..size).step_by(2) {
+        for x in 0..size {
+            let top = is_dark(x, y);
+            let bottom = y + 1 < size && is_dark(x, y + 1);
+            if options.colors {
+                let fg = if top { ANSI_DARK.0 } else { ANSI_LIGHT.0 };
+                let bg = if bottom { ANSI_DARK.1 } else { ANSI_LIGHT.1 };
+                out.push_str(&format!("\x1b[{};{}m▀", fg, bg));
+            } else {
+                let (top, bottom) = if options.dark_background {
+                    (!top, y + 1 < size && !bottom)
+                } else {
+                    (top, bottom)
+                };
+                out.push(match (top, bottom) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                });
+            }
+        }
+        if options.colors {
+            out.push_str(ANSI_RESET);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn draw_frame(
+    out: &mut impl std::io::Write,
+    matrix: &(impl Matrix + ?Sized),
+    caption: &str,
+    options: &TerminalOptions,
+) -> std::io::Result<()> {
+    writeln!(
+        out,
+        "{}{}{}\x1b[K",
+        ANSI_CURSOR_HOME,
+        render_terminal(matrix, options),
+        caption
+    )?;
+    out.flush()
+}
+
+// Draws each frame in place at a fixed rate until `frames` runs out; pass an
+// endless iterator to loop.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn play_terminal<M: Matrix>(
+    out: &mut impl std::io::Write,
+    frames: impl IntoIterator<Item = (M, String)>,
+    options: &TerminalOptions,
+    fps: u32,
+) -> std::io::Result<()> {
+    let delay = std::time::Duration::from_secs(1) / fps.max(1);
+
+    write!(out, "{}{}", ANSI_CLEAR_SCREEN, ANSI_CURSOR_HOME)?;
+    for (matrix, caption) in frames {
+        let started = std::time::Instant::now();
+        draw_frame(out, &matrix, &caption, options)?;
+        std::thread::sleep(delay.saturating_sub(started.elapsed()));
+    }
+    Ok(())
+}
+
+// Plays the encoder's sequence like the web player: manifest first, looping
+// forever unless `loops` is given, with fresh droplets on every fountain pass.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn play_encoder(
+    out: &mut impl std::io::Write,
+    encoder: &Encoder,
+    options: &TerminalOptions,
+    fps: u32,
+    loops: Option<usize>,
+) -> std::io::Result<()> {
+    let total = encoder.frame_count() + 1;
+    let limit = loops.map_or(usize::MAX, |loops| loops.saturating_mul(total));
+    let frames = (0..).flat_map(|_| encoder.frames()).take(limit);
+    let delay = std::time::Duration::from_secs(1) / fps.max(1);
+
+    write!(out, "{}{}", ANSI_CLEAR_SCREEN, ANSI_CURSOR_HOME)?;
+    for (position, frame) in frames.enumerate() {
+        let started = std::time::Instant::now();
+        let frame = frame.map_err(std::io::Error::other)?;
+        let caption = frame_caption(position % total, total, &frame.payload);
+        draw_frame(out, &frame.code, &caption, options)?;
+        std::thread::sleep(delay.saturating_sub(started.elapsed()));
+    }
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use qsc_core::qrcode::{EcLevel, QrCode, Version};
+use qsc_core::{
+    AnimationFormat, AnimationOptions, Error, MAX_ANIMATION_SIZE, rasterize_luma, render_animation,
+};
+use std::io::Cursor;
+
+fn sample_codes() -> Vec<QrCode> {
+    (0..4u8)
+        .map(|i| QrCode::with_version([i; 8], Version::Normal(3), EcLevel::M).unwrap())
+        .collect()
+}
+
+#[test]
+fn gif_loops_every_frame_with_delay_and_scale() {
+    let codes = sample_codes();
+    let options = AnimationOptions {
+        format: AnimationFormat::Gif,
+        frame_delay_ms: 150,
+        scale: 3,
+        quiet_zone: 2,
+    };
+    let gif = render_animation(&codes, &options).unwrap();
+
+    let mut decoder = gif::DecodeOptions::new();
+    decoder.set_color_output(gif::ColorOutput::Indexed);
+    let mut decoder = decoder.read_info(Cursor::new(gif)).unwrap();
+    let size = (29 + 4) * 3;
+    assert_eq!(decoder.width() as u32, size);
+
+    let mut decoded = 0;
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        assert_eq!(frame.delay, 15);
+        let expected: Vec<u8> = rasterize_luma(&codes[decoded], size, 2)
+            .into_iter()
+            .map(|value| value >> 7)
+            .collect();
+        assert_eq!(&frame.buffer[..], &expected[..]);
+        decoded += 1;
+    }
+    assert_eq!(decoded, codes.len());
+}
+
+#[test]
+fn apng_holds_every_frame() {
+    let codes = sample_codes();
+    let options = AnimationOptions {
+        format: AnimationFormat::Apng,
+        frame_delay_ms: 250,
+        ..AnimationOptions::default()
+    };
+    let apng = render_animation(&codes, &options).unwrap();
+
+    let mut reader = png::Decoder::new(Cursor::new(apng)).read_info().unwrap();
+    let control = reader.info().animation_control.unwrap();
+    assert_eq!(control.num_frames, codes.len() as u32);
+    assert_eq!(control.num_plays, 0);
+
+    let size = options.canvas_size(29).unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+    for code in &codes {
+        reader.next_frame(&mut pixels).unwrap();
+        let delay = reader.info().frame_control.unwrap();
+        assert_eq!((delay.delay_num, delay.delay_den), (250, 1000));
+        assert_eq!(pixels, rasterize_luma(code, size, options.quiet_zone));
+    }
+}
+
+#[test]
+fn oversized_canvas_is_rejected_for_both_formats() {
+    let codes = sample_codes();
+    for format in [AnimationFormat::Gif, AnimationFormat::Apng] {
+        for (scale, quiet_zone) in [(u32::MAX, 4), (4, u32::MAX), (MAX_ANIMATION_SIZE, 0)] {
+            let options = AnimationOptions {
+                format,
+                scale,
+                quiet_zone,
+                ..AnimationOptions::default()
+            };
+            assert!(options.canvas_size(29).is_err());
+            assert!(matches!(
+                render_animation(&codes, &options),
+                Err(Error::ImageEncoding(_))
+            ));
+        }
+    }
+}
+
+#[test]
+fn empty_sequence_is_rejected() {
+    let codes: Vec<QrCode> = Vec::new();
+    assert!(render_animation(&codes, &AnimationOptions::default()).is_err());
+}
@@ -0,0 +1,57 @@
+use qsc_core::qrcode::{EcLevel, QrCode, Version};
+use qsc_core::{DEFAULT_QUIET_ZONE, build_png_zip, rasterize_luma, render_png};
+use std::io::{Cursor, Read};
+use zip::ZipArchive;
+
+fn decode_png(bytes: &[u8]) -> (u32, Vec<u8>) {
+    let decoder = png::Decoder::new(Cursor::new(bytes));
+    let mut reader = decoder.read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+    assert_eq!(info.width, info.height);
+    pixels.truncate(info.buffer_size());
+    (info.width, pixels)
+}
+
+#[test]
+fn png_matches_the_canvas_raster() {
+    let code = QrCode::with_version(b"hello", Version::Normal(4), EcLevel::Q).unwrap();
+    let png = render_png(&code, 200, DEFAULT_QUIET_ZONE).unwrap();
+
+    let (size, pixels) = decode_png(&png);
+    assert_eq!(size, 200);
+    assert_eq!(pixels, rasterize_luma(&code, 200, DEFAULT_QUIET_ZONE));
+}
+
+#[test]
+fn zip_holds_one_numbered_png_per_frame() {
+    let codes: Vec<QrCode> = (0..3u8)
+        .map(|i| QrCode::with_version([i], Version::Normal(2), EcLevel::L).unwrap())
+        .collect();
+    let zip = build_png_zip(&codes, 120, DEFAULT_QUIET_ZONE).unwrap();
+
+    let mut archive = ZipArchive::new(Cursor::new(zip)).unwrap();
+    let mut names: Vec<String> = archive.file_names().map(str::to_string).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        [
+            "frame-0001-of-0003.png",
+            "frame-0002-of-0003.png",
+            "frame-0003-of-0003.png"
+        ]
+    );
+
+    for (index, code) in codes.iter().enumerate() {
+        let mut png = Vec::new();
+        archive
+            .by_name(&names[index])
+            .unwrap()
+            .read_to_end(&mut png)
+            .unwrap();
+        assert_eq!(
+            decode_png(&png).1,
+            rasterize_luma(code, 120, DEFAULT_QUIET_ZONE)
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use qsc_core::qrcode::{EcLevel, QrCode, Version};
+use qsc_core::{
+    EncodeOptions, Encoder, FRAME_HEADER_SIZE, MANIFEST_SIZE, encode_byte_mode, get_max_qr_capacity,
+};
+
+const EC_LEVELS: [EcLevel; 4] = [EcLevel::L, EcLevel::M, EcLevel::Q, EcLevel::H];
+
+fn sample_data(len: usize) -> Vec<u8> {
+    let mut state = 0x9e37_79b9_u32;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect()
+}
+
+#[test]
+fn matches_byte_mode_table() {
+    assert_eq!(get_max_qr_capacity(Version::Normal(1), EcLevel::L), 17);
+    assert_eq!(get_max_qr_capacity(Version::Normal(1), EcLevel::H), 7);
+    assert_eq!(get_max_qr_capacity(Version::Normal(8), EcLevel::L), 192);
+    assert_eq!(get_max_qr_capacity(Version::Normal(10), EcLevel::M), 213);
+    assert_eq!(get_max_qr_capacity(Version::Normal(40), EcLevel::L), 2953);
+    assert_eq!(get_max_qr_capacity(Version::Normal(40), EcLevel::H), 1273);
+}
+
+#[test]
+fn one_byte_over_capacity_does_not_fit() {
+    for version in 1..=40 {
+        for ec_level in EC_LEVELS {
+            let version = Version::Normal(version);
+            let capacity = get_max_qr_capacity(version, ec_level);
+
+            let over = vec![0xff; capacity + 1];
+            assert!(
+                encode_byte_mode(&over, version, ec_level).is_err(),
+                "{:?}/{:?}: {} bytes should not fit",
+                version,
+                ec_level,
+                capacity + 1
+            );
+            // Nothing cleverer than byte mode can do better on arbitrary bytes.
+            assert!(QrCode::with_version(&over, version, ec_level).is_err());
+        }
+    }
+}
+
+#[test]
+fn every_full_chunk_encodes() {
+    for version in 1..=40 {
+        for ec_level in EC_LEVELS {
+            let version = Version::Normal(version);
+            let capacity = get_max_qr_capacity(version, ec_level);
+
+            // Holds for every pair, even those too small to carry a manifest.
+            let full = encode_byte_mode(&vec![0xff; capacity], version, ec_level)
+                .unwrap_or_else(|e| panic!("{:?}/{:?}: {}", version, ec_level, e));
+            assert_eq!(full.version(), version);
+            assert!(encode_byte_mode(&vec![0xff; capacity + 1], version, ec_level).is_err());
+
+            let options = EncodeOptions {
+                version,
+                ec_level,
+                ..EncodeOptions::default()
+            };
+
+            let Ok(encoder) = Encoder::new(sample_data(capacity * 2 + 1), options) else {
+                // Too small to carry the manifest, rejected up front.
+                assert!(
+                    capacity < FRAME_HEADER_SIZE + MANIFEST_SIZE,
+                    "{:?}/{:?}",
+                    version,
+                    ec_level
+                );
+                continue;
+            };
+            assert_eq!(encoder.chunk_capacity(), capacity - FRAME_HEADER_SIZE);
+            assert_eq!(encoder.frame_count(), 3);
+
+            let frame = encoder.frame(0).unwrap();
+            assert_eq!(
+                frame.payload.len(),
+                capacity,
+                "{:?}/{:?}",
+                version,
+                ec_level
+            );
+            assert_eq!(frame.code.version(), version);
+        }
+    }
+}
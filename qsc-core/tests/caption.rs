@@ -0,0 +1,19 @@
+use qsc_core::{frame_caption, frame_checksum, short_checksum};
+
+#[test]
+fn caption_shows_position_total_and_short_checksum() {
+    let payload = b"QS frame payload";
+    let checksum = format!("{:08x}", frame_checksum(payload));
+
+    assert_eq!(short_checksum(payload), checksum[..4]);
+    assert_eq!(
+        frame_caption(11, 40, payload),
+        format!("12 / 40 · {}", &checksum[..4])
+    );
+}
+
+#[test]
+fn checksum_is_crc32() {
+    assert_eq!(frame_checksum(b"123456789"), 0xcbf4_3926);
+    assert_eq!(short_checksum(b"123456789"), "cbf4");
+}
@@ -0,0 +1,73 @@
+use qsc_core::{Compression, EncodeOptions, Encoder, Reassembler, TransferMode};
+
+fn json_log(lines: usize) -> Vec<u8> {
+    (0..lines)
+        .map(|i| {
+            format!(
+                "{{\"ts\":\"2024-01-01T00:00:{:02}Z\",\"level\":\"info\",\"msg\":\"request handled\",\"id\":{}}}\n",
+                i % 60,
+                i
+            )
+        })
+        .collect::<String>()
+        .into_bytes()
+}
+
+fn reassemble(encoder: &Encoder, passes: usize) -> Vec<u8> {
+    let mut reassembler = Reassembler::new();
+    reassembler.push(&encoder.manifest_payload()).unwrap();
+    for index in 0..encoder.frame_count() * passes {
+        reassembler.push(&encoder.frame_payload(index)).unwrap();
+        if reassembler.is_complete() {
+            break;
+        }
+    }
+    reassembler.finish().unwrap()
+}
+
+#[test]
+fn auto_compression_reduces_frames_and_round_trips() {
+    let data = json_log(1_500);
+
+    let raw = Encoder::new(
+        data.as_slice(),
+        EncodeOptions {
+            compression: Compression::None,
+            ..EncodeOptions::default()
+        },
+    )
+    .unwrap();
+    let auto = Encoder::new(data.as_slice(), EncodeOptions::default()).unwrap();
+
+    assert_eq!(auto.compression(), Compression::Deflate);
+    assert!(auto.frame_count() * 4 < raw.frame_count());
+    assert_eq!(reassemble(&auto, 1), data);
+}
+
+#[test]
+fn auto_compression_skips_incompressible_data() {
+    let data: Vec<u8> = (0..64u32)
+        .flat_map(|i| i.wrapping_mul(2_654_435_761).to_be_bytes())
+        .collect();
+    let encoder = Encoder::new(data.as_slice(), EncodeOptions::default()).unwrap();
+
+    assert_eq!(encoder.compression(), Compression::None);
+    assert_eq!(encoder.stream_len(), data.len());
+    assert_eq!(reassemble(&encoder, 1), data);
+}
+
+#[test]
+fn fountain_mode_carries_compressed_stream() {
+    let data = json_log(800);
+    let encoder = Encoder::new(
+        data.as_slice(),
+        EncodeOptions {
+            mode: TransferMode::Fountain,
+            compression: Compression::Deflate,
+            ..EncodeOptions::default()
+        },
+    )
+    .unwrap();
+
+    assert_eq!(reassemble(&encoder, 4), data);
+}
@@ -0,0 +1,143 @@
+use qsc_core::{
+    EncodeOptions, Encoder, Error, FLAG_FOUNTAIN, FountainDecoder, FrameHeader,
+    MAX_SEQUENCE_FRAMES, PushOutcome, Reassembler, TransferMode, encode_frame,
+};
+
+fn sample_data(len: usize) -> Vec<u8> {
+    let mut state = len as u32 ^ 0x9e37_79b9;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect()
+}
+
+fn fountain_encoder(data: &[u8]) -> Encoder {
+    let options = EncodeOptions {
+        mode: TransferMode::Fountain,
+        ..EncodeOptions::default()
+    };
+    Encoder::new(data, options).unwrap()
+}
+
+fn droplets_needed(encoder: &Encoder, seeds: impl Iterator<Item = usize>) -> (usize, Vec<u8>) {
+    let mut reassembler = Reassembler::new();
+    reassembler.push(&encoder.manifest_payload()).unwrap();
+    for (count, seed) in seeds.enumerate() {
+        reassembler.push(&encoder.frame_payload(seed)).unwrap();
+        if reassembler.is_complete() {
+            return (count + 1, reassembler.finish().unwrap());
+        }
+    }
+    panic!("fountain decoder never completed");
+}
+
+#[test]
+fn recovers_from_a_full_systematic_pass() {
+    let data = sample_data(10_000);
+    let encoder = fountain_encoder(&data);
+
+    let (needed, decoded) = droplets_needed(&encoder, 0..encoder.frame_count());
+    assert_eq!(needed, encoder.frame_count());
+    assert_eq!(decoded, data);
+}
+
+#[test]
+fn recovers_from_coded_droplets_only() {
+    let data = sample_data(40_000);
+    let encoder = fountain_encoder(&data);
+    let block_count = encoder.frame_count();
+
+    let (needed, decoded) = droplets_needed(&encoder, block_count..block_count * 10);
+    assert_eq!(decoded, data);
+    assert!(
+        needed < block_count * 2,
+        "needed {} droplets for {} blocks",
+        needed,
+        block_count
+    );
+}
+
+#[test]
+fn recovers_when_every_third_frame_is_lost() {
+    let data = sample_data(25_000);
+    let encoder = fountain_encoder(&data);
+
+    let (_, decoded) = droplets_needed(&encoder, (0..).filter(|seed| seed % 3 != 1));
+    assert_eq!(decoded, data);
+}
+
+#[test]
+fn ignores_repeated_droplets_and_handles_tiny_input() {
+    let data = b"x".to_vec();
+    let encoder = fountain_encoder(&data);
+    assert_eq!(encoder.frame_count(), 1);
+
+    let droplet = encoder.frame_payload(5);
+    let mut reassembler = Reassembler::new();
+    reassembler.push(&encoder.manifest_payload()).unwrap();
+    assert_eq!(reassembler.push(&droplet).unwrap(), PushOutcome::Accepted);
+    assert_eq!(reassembler.push(&droplet).unwrap(), PushOutcome::Duplicate);
+    assert_eq!(reassembler.finish().unwrap(), data);
+}
+
+#[test]
+fn rejects_empty_and_implausible_droplets() {
+    let droplet = |total: u32, payload: &[u8]| {
+        let header = FrameHeader::new(1, 5, total, payload.len() as u16).with_flags(FLAG_FOUNTAIN);
+        Reassembler::new().push(&encode_frame(&header, payload))
+    };
+
+    assert!(matches!(droplet(100, &[]), Err(Error::InvalidHeader(_))));
+    assert!(matches!(
+        droplet(u32::MAX, &[0; 4]),
+        Err(Error::InvalidHeader(_))
+    ));
+
+    let largest = (MAX_SEQUENCE_FRAMES * 4) as u32;
+    assert_eq!(droplet(largest, &[0; 4]).unwrap(), PushOutcome::Accepted);
+    assert!(matches!(
+        droplet(largest + 1, &[0; 4]),
+        Err(Error::InvalidHeader(_))
+    ));
+}
+
+#[test]
+fn pending_droplets_are_released_and_capped() {
+    let (session_id, data_len, block_size) = (7, 2_000, 10);
+    let mut decoder = FountainDecoder::new(session_id, data_len, block_size);
+    let block_count = decoder.block_count();
+    let mut peak = 0;
+    for seed in block_count as u32.. {
+        decoder.push(seed, &[seed as u8; 10]).unwrap();
+        peak = peak.max(decoder.pending_droplets());
+        if decoder.is_complete() {
+            break;
+        }
+    }
+    assert!(peak > 0 && peak < decoder.max_pending_droplets());
+    assert_eq!(decoder.pending_droplets(), 0);
+
+    // Only droplets of degree two or more, so peeling never starts.
+    let mut decoder = FountainDecoder::new(session_id, data_len, block_size);
+    let max = decoder.max_pending_droplets();
+    let mut hostile = (block_count as u32..).filter(|&seed| {
+        let mut probe = FountainDecoder::new(session_id, data_len, block_size);
+        probe.push(seed, &[0; 10]).unwrap();
+        probe.solved() == 0
+    });
+    for seed in hostile.by_ref().take(max) {
+        assert!(decoder.push(seed, &[0; 10]).unwrap());
+    }
+    assert_eq!(decoder.pending_droplets(), max);
+    let seed = hostile.next().unwrap();
+    assert_eq!(
+        decoder.push(seed, &[0; 10]),
+        Err(Error::TooManyPendingDroplets { max })
+    );
+    assert_eq!(decoder.pending_droplets(), max);
+    assert_eq!(decoder.solved(), 0);
+}
@@ -0,0 +1,69 @@
+use qsc_core::qrcode::{EcLevel, Version};
+use qsc_core::{
+    DEFAULT_QUIET_ZONE, EncodeOptions, Encoder, Error, check_canvas_size, parse_ec_level,
+};
+
+#[test]
+fn rejects_versions_outside_one_to_forty() {
+    for version in [Version::Normal(0), Version::Normal(41), Version::Micro(2)] {
+        let options = EncodeOptions {
+            version,
+            ..EncodeOptions::default()
+        };
+        assert_eq!(
+            Encoder::new(b"hello".to_vec(), options).err(),
+            Some(Error::UnsupportedVersion(version))
+        );
+    }
+}
+
+#[test]
+fn higher_ec_levels_shrink_chunk_capacity() {
+    let capacities: Vec<usize> = [EcLevel::L, EcLevel::M, EcLevel::Q, EcLevel::H]
+        .into_iter()
+        .map(|ec_level| {
+            let options = EncodeOptions {
+                version: Version::Normal(10),
+                ec_level,
+                ..EncodeOptions::default()
+            };
+            Encoder::new(b"hello".to_vec(), options)
+                .unwrap()
+                .chunk_capacity()
+        })
+        .collect();
+
+    assert!(capacities.windows(2).all(|pair| pair[0] > pair[1]));
+}
+
+#[test]
+fn canvas_must_give_every_module_two_pixels() {
+    assert_eq!(
+        check_canvas_size(Version::Normal(8), 200, DEFAULT_QUIET_ZONE),
+        Ok(())
+    );
+    assert_eq!(
+        check_canvas_size(Version::Normal(25), 200, DEFAULT_QUIET_ZONE),
+        Err(Error::CanvasTooSmall {
+            canvas_size: 200,
+            min_size: 250,
+        })
+    );
+    assert_eq!(check_canvas_size(Version::Normal(40), 354, 0), Ok(()));
+    assert!(check_canvas_size(Version::Normal(40), 354, DEFAULT_QUIET_ZONE).is_err());
+    assert_eq!(
+        check_canvas_size(Version::Normal(1), 200, u32::MAX / 2),
+        Err(Error::QuietZoneTooLarge(u32::MAX / 2))
+    );
+    assert_eq!(
+        check_canvas_size(Version::Normal(1), 200, u32::MAX / 4),
+        Err(Error::QuietZoneTooLarge(u32::MAX / 4))
+    );
+}
+
+#[test]
+fn parses_ec_levels_case_insensitively() {
+    assert_eq!(parse_ec_level("q"), Some(EcLevel::Q));
+    assert_eq!(parse_ec_level("H"), Some(EcLevel::H));
+    assert_eq!(parse_ec_level("X"), None);
+}
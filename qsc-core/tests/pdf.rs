@@ -0,0 +1,86 @@
+use qsc_core::qrcode::{EcLevel, QrCode, Version};
+use qsc_core::{
+    Error, MAX_PAPER_COLUMNS, PageSize, PaperFrame, PaperOptions, frame_checksum, render_pdf,
+};
+
+fn sample_frames(count: usize) -> Vec<PaperFrame<QrCode>> {
+    (0..count)
+        .map(|i| {
+            let payload = format!("frame {}", i).into_bytes();
+            PaperFrame {
+                code: QrCode::with_version(&payload, Version::Normal(4), EcLevel::Q).unwrap(),
+                checksum: frame_checksum(&payload),
+            }
+        })
+        .collect()
+}
+
+fn contains(haystack: &[u8], needle: &str) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle.as_bytes())
+}
+
+#[test]
+fn labels_every_frame_and_paginates() {
+    let frames = sample_frames(11);
+    let pdf = render_pdf("backup.tar", &frames, &PaperOptions::default()).unwrap();
+
+    assert!(pdf.starts_with(b"%PDF-"));
+    assert!(contains(&pdf, "/Count 2"));
+    assert!(contains(&pdf, "/MediaBox [0 0 595.276 841.89]"));
+    assert!(contains(&pdf, "(backup.tar - 11 frames - page 2 / 2)"));
+    for (index, frame) in frames.iter().enumerate() {
+        assert!(contains(&pdf, &format!("(Frame {} / 11)", index + 1)));
+        assert!(contains(&pdf, &format!("(CRC32 {:08x})", frame.checksum)));
+    }
+}
+
+#[test]
+fn letter_pages_and_columns_are_configurable() {
+    let options = PaperOptions {
+        page_size: PageSize::Letter,
+        columns: 1,
+        ..PaperOptions::default()
+    };
+    let pdf = render_pdf("secret.txt", &sample_frames(3), &options).unwrap();
+
+    assert!(contains(&pdf, "/MediaBox [0 0 612 792]"));
+    // One large code per row leaves room for a single frame per page.
+    assert!(contains(&pdf, "/Count 3"));
+}
+
+#[test]
+fn non_ascii_file_names_are_replaced() {
+    let pdf = render_pdf("résumé.pdf", &sample_frames(1), &PaperOptions::default()).unwrap();
+    assert!(contains(&pdf, "(r?sum?.pdf)"));
+}
+
+#[test]
+fn empty_sequence_is_rejected() {
+    let frames: Vec<PaperFrame<QrCode>> = Vec::new();
+    assert_eq!(
+        render_pdf("empty", &frames, &PaperOptions::default()),
+        Err(Error::EmptySequence)
+    );
+}
+
+#[test]
+fn clamps_columns_so_cells_keep_a_positive_size() {
+    let frames = sample_frames(60);
+    let clamped = PaperOptions {
+        columns: MAX_PAPER_COLUMNS,
+        ..PaperOptions::default()
+    };
+    let expected = render_pdf("many.bin", &frames, &clamped).unwrap();
+
+    for columns in [MAX_PAPER_COLUMNS + 1, 1_000, usize::MAX] {
+        let options = PaperOptions {
+            columns,
+            ..PaperOptions::default()
+        };
+        assert_eq!(render_pdf("many.bin", &frames, &options).unwrap(), expected);
+    }
+    // Eight columns of seven rows fit 56 codes on an A4 page.
+    assert!(contains(&expected, "/Count 2"));
+}
@@ -0,0 +1,181 @@
+use qsc_core::{
+    EncodeOptions, Encoder, Error, FLAG_DEFLATE, FrameHeader, MAX_SEQUENCE_FRAMES, PushOutcome,
+    Reassembler, decode_frame, encode_frame,
+};
+
+fn sample_data(len: usize) -> Vec<u8> {
+    let mut state = len as u32 ^ 0x9e37_79b9;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect()
+}
+
+fn frame_payloads(data: &[u8]) -> Vec<Vec<u8>> {
+    let encoder = Encoder::new(data, EncodeOptions::default()).unwrap();
+    (0..encoder.frame_count())
+        .map(|index| encoder.frame_payload(index))
+        .collect()
+}
+
+fn manifest_payload(data: &[u8]) -> Vec<u8> {
+    Encoder::new(data, EncodeOptions::default())
+        .unwrap()
+        .manifest_payload()
+}
+
+#[test]
+fn round_trips_frames_in_any_order_with_duplicates() {
+    let data = sample_data(5_000);
+    let mut frames = frame_payloads(&data);
+    assert!(frames.len() > 2);
+
+    frames.reverse();
+    frames.push(frames[1].clone());
+    frames.insert(2, manifest_payload(&data));
+
+    let mut reassembler = Reassembler::new();
+    let mut duplicates = 0;
+    for frame in &frames {
+        if reassembler.push(frame).unwrap() == PushOutcome::Duplicate {
+            duplicates += 1;
+        }
+    }
+
+    assert_eq!(duplicates, 1);
+    assert!(reassembler.is_complete());
+    assert_eq!(reassembler.finish().unwrap(), data);
+}
+
+#[test]
+fn reports_progress_and_missing_indices() {
+    let data = sample_data(3_000);
+    let frames = frame_payloads(&data);
+    let total = frames.len();
+
+    let mut reassembler = Reassembler::new();
+    assert_eq!(reassembler.progress().total, None);
+
+    reassembler.push(&frames[0]).unwrap();
+    reassembler.push(&frames[total - 1]).unwrap();
+
+    let progress = reassembler.progress();
+    assert_eq!(progress.received, 2);
+    assert_eq!(progress.total, Some(total));
+    assert!(!progress.manifest);
+    assert_eq!(
+        reassembler.missing_indices(),
+        (1..total as u32 - 1).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        reassembler.finish().unwrap_err(),
+        Error::Incomplete { missing: total - 2 }
+    );
+}
+
+#[test]
+fn round_trips_empty_input() {
+    let frames = frame_payloads(&[]);
+    assert_eq!(frames.len(), 1);
+
+    let mut reassembler = Reassembler::new();
+    reassembler.push(&frames[0]).unwrap();
+    reassembler.push(&manifest_payload(&[])).unwrap();
+    assert_eq!(reassembler.finish().unwrap(), Vec::<u8>::new());
+}
+
+#[test]
+fn rejects_frames_from_another_session() {
+    let first = frame_payloads(b"first file");
+    let second = frame_payloads(b"second file");
+
+    let mut reassembler = Reassembler::new();
+    reassembler.push(&first[0]).unwrap();
+    assert!(matches!(
+        reassembler.push(&second[0]),
+        Err(Error::SessionMismatch { .. })
+    ));
+}
+
+#[test]
+fn rejects_implausible_frame_totals_before_allocating() {
+    for total in [0, MAX_SEQUENCE_FRAMES as u32 + 1, u32::MAX] {
+        let frame = encode_frame(&FrameHeader::new(1, 0, total, 3), b"abc");
+        assert!(matches!(
+            Reassembler::new().push(&frame),
+            Err(Error::InvalidHeader(_))
+        ));
+    }
+
+    let frame = encode_frame(
+        &FrameHeader::new(1, 0, MAX_SEQUENCE_FRAMES as u32, 3),
+        b"abc",
+    );
+    assert_eq!(
+        Reassembler::new().push(&frame).unwrap(),
+        PushOutcome::Accepted
+    );
+}
+
+#[test]
+fn rejected_frames_do_not_change_the_compression_flag() {
+    let data = b"compressible ".repeat(400);
+    let mut frames = frame_payloads(&data);
+    frames.push(manifest_payload(&data));
+    let (header, payload) = decode_frame(&frames[0]).unwrap();
+    assert!(header.is_deflated());
+    let plain = FrameHeader {
+        flags: header.flags & !FLAG_DEFLATE,
+        ..header
+    };
+
+    // Turned away for its total before its flags could be recorded.
+    let mut reassembler = Reassembler::new();
+    let forged = FrameHeader { total: 0, ..plain };
+    assert!(reassembler.push(&encode_frame(&forged, payload)).is_err());
+    for frame in &frames {
+        reassembler.push(frame).unwrap();
+    }
+    assert!(matches!(
+        reassembler.push(&encode_frame(&plain, payload)),
+        Err(Error::CompressionMismatch)
+    ));
+    assert_eq!(reassembler.finish().unwrap(), data);
+}
+
+#[test]
+fn requires_the_manifest_to_finish() {
+    let data = sample_data(1_000);
+
+    let mut reassembler = Reassembler::new();
+    for frame in frame_payloads(&data) {
+        reassembler.push(&frame).unwrap();
+    }
+
+    assert!(!reassembler.is_complete());
+    assert_eq!(reassembler.finish().unwrap_err(), Error::MissingManifest);
+}
+
+#[test]
+fn reports_digest_mismatch_for_corrupted_payload() {
+    let data = sample_data(4_000);
+    let mut frames = frame_payloads(&data);
+    let last = frames[1].len() - 1;
+    frames[1][last] ^= 0xff;
+
+    let mut reassembler = Reassembler::new();
+    reassembler.push(&manifest_payload(&data)).unwrap();
+    for frame in &frames {
+        reassembler.push(frame).unwrap();
+    }
+
+    assert!(reassembler.is_complete());
+    assert!(matches!(
+        reassembler.finish(),
+        Err(Error::DigestMismatch { .. })
+    ));
+}
@@ -0,0 +1,84 @@
+use qsc_core::qrcode::{EcLevel, QrCode, Version};
+use qsc_core::{DEFAULT_QUIET_ZONE, Matrix, module_pixels, rasterize_rgba, symbol_offset};
+
+const DARK: [u8; 4] = [0, 0, 0, 255];
+const LIGHT: [u8; 4] = [255, 255, 255, 255];
+
+fn pixel(pixels: &[u8], size: u32, x: u32, y: u32) -> [u8; 4] {
+    let offset = ((y * size + x) * 4) as usize;
+    pixels[offset..offset + 4].try_into().unwrap()
+}
+
+#[test]
+fn rasterizes_dark_modules_black_on_integer_grid() {
+    let code = QrCode::with_version(b"hello", Version::Normal(8), EcLevel::L).unwrap();
+    let canvas_size = 200;
+    let module = module_pixels(code.width(), canvas_size, DEFAULT_QUIET_ZONE);
+    let offset = symbol_offset(code.width(), canvas_size, DEFAULT_QUIET_ZONE);
+    assert_eq!(module, 3);
+    assert_eq!(offset, (200 - 49 * 3) / 2);
+
+    let pixels = rasterize_rgba(&code, canvas_size, DEFAULT_QUIET_ZONE);
+    assert_eq!(pixels.len(), (canvas_size * canvas_size * 4) as usize);
+
+    for y in 0..code.width() as u32 {
+        for x in 0..code.width() as u32 {
+            let expected = if code.is_dark(x as usize, y as usize) {
+                DARK
+            } else {
+                LIGHT
+            };
+            for (dx, dy) in [
+                (0, 0),
+                (module - 1, 0),
+                (0, module - 1),
+                (module - 1, module - 1),
+            ] {
+                let (px, py) = (offset + x * module + dx, offset + y * module + dy);
+                assert_eq!(pixel(&pixels, canvas_size, px, py), expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn quiet_zone_stays_light_and_symbol_is_centred() {
+    let code = QrCode::with_version(b"hello", Version::Normal(3), EcLevel::M).unwrap();
+    let canvas_size = 160;
+    let module = module_pixels(code.width(), canvas_size, DEFAULT_QUIET_ZONE);
+    let offset = symbol_offset(code.width(), canvas_size, DEFAULT_QUIET_ZONE);
+    assert_eq!(module, 4);
+    assert!(offset >= DEFAULT_QUIET_ZONE * module);
+    assert_eq!(
+        canvas_size - offset - code.width() as u32 * module,
+        offset,
+        "symbol should be centred"
+    );
+
+    let pixels = rasterize_rgba(&code, canvas_size, DEFAULT_QUIET_ZONE);
+    for i in 0..canvas_size {
+        for edge in [0, offset - 1, offset + code.width() as u32 * module] {
+            assert_eq!(pixel(&pixels, canvas_size, i, edge), LIGHT);
+            assert_eq!(pixel(&pixels, canvas_size, edge, i), LIGHT);
+        }
+    }
+}
+
+#[test]
+fn zero_quiet_zone_fills_the_canvas() {
+    let code = QrCode::with_version(b"hello", Version::Normal(1), EcLevel::L).unwrap();
+    assert_eq!(module_pixels(code.width(), 210, 0), 10);
+    assert_eq!(symbol_offset(code.width(), 210, 0), 0);
+}
+
+#[test]
+fn oversized_quiet_zone_leaves_no_room_for_modules() {
+    let code = QrCode::with_version(b"hello", Version::Normal(1), EcLevel::L).unwrap();
+    assert_eq!(module_pixels(code.width(), 210, u32::MAX / 2), 0);
+    assert_eq!(module_pixels(code.width(), 210, u32::MAX), 0);
+    assert!(
+        rasterize_rgba(&code, 8, u32::MAX)
+            .chunks_exact(4)
+            .all(|p| p == LIGHT)
+    );
+}
@@ -0,0 +1,117 @@
+use proptest::collection::vec;
+use proptest::prelude::*;
+use qsc_core::qrcode::{EcLevel, Version};
+use qsc_core::{
+    Compression, DEFAULT_QUIET_ZONE, EncodeOptions, Encoder, FRAME_HEADER_SIZE, MANIFEST_SIZE,
+    MIN_MODULE_PIXELS, Reassembler, get_max_qr_capacity, module_pixels, rasterize_rgba, scan_luma,
+};
+
+// The browser's default canvas, `DEFAULT_QR_CODE_SIZE` in qsc-generator.
+const CANVAS_SIZE: u32 = 200;
+
+// Largest version that still gets `MIN_MODULE_PIXELS` on the canvas.
+const MAX_CANVAS_VERSION: i16 = 18;
+
+const EC_LEVELS: [EcLevel; 4] = [EcLevel::L, EcLevel::M, EcLevel::Q, EcLevel::H];
+
+fn options(version: i16, ec_level: EcLevel) -> EncodeOptions {
+    EncodeOptions {
+        version: Version::Normal(version),
+        ec_level,
+        compression: Compression::None,
+        ..EncodeOptions::default()
+    }
+}
+
+fn chunk_capacity(version: i16, ec_level: EcLevel) -> usize {
+    get_max_qr_capacity(Version::Normal(version), ec_level).saturating_sub(FRAME_HEADER_SIZE)
+}
+
+// Canvas 0 holds the manifest and canvas i the data frame i - 1, as in
+// `create_canvas_sequence`; each is drawn like `render_qrcode_canvas` and read
+// back through its red channel before scanning.
+fn round_trip(data: &[u8], options: EncodeOptions) -> Result<Vec<u8>, TestCaseError> {
+    let encoder = Encoder::new(data, options).unwrap();
+    let mut reassembler = Reassembler::new();
+
+    for index in 0..=encoder.frame_count() {
+        let frame = match index {
+            0 => encoder.manifest_frame(),
+            _ => encoder.frame(index - 1),
+        }
+        .unwrap();
+        prop_assert!(
+            module_pixels(frame.code.width(), CANVAS_SIZE, DEFAULT_QUIET_ZONE) >= MIN_MODULE_PIXELS
+        );
+
+        let canvas = rasterize_rgba(&frame.code, CANVAS_SIZE, DEFAULT_QUIET_ZONE);
+        let luma: Vec<u8> = canvas.chunks_exact(4).map(|pixel| pixel[0]).collect();
+        let codes = scan_luma(&luma, CANVAS_SIZE, CANVAS_SIZE);
+        prop_assert_eq!(codes.len(), 1, "canvas {}", index);
+        reassembler.push(&codes[0].data).unwrap();
+    }
+
+    prop_assert!(reassembler.is_complete());
+    Ok(reassembler.finish().unwrap())
+}
+
+#[test]
+fn default_canvas_round_trips_chunk_boundaries() {
+    let version = qsc_core::DEFAULT_QR_CODE_VERSION;
+    let capacity = chunk_capacity(version, EcLevel::L);
+
+    for (len, frames) in [
+        (0, 1),
+        (1, 1),
+        (capacity, 1),
+        (capacity + 1, 2),
+        (2 * capacity, 2),
+    ] {
+        let data: Vec<u8> = (0..len).map(|i| (i * 151 % 256) as u8).collect();
+        let options = options(version, EcLevel::L);
+        assert_eq!(
+            Encoder::new(&data[..], options.clone())
+                .unwrap()
+                .frame_count(),
+            frames
+        );
+        assert_eq!(round_trip(&data, options).unwrap(), data, "{} bytes", len);
+    }
+}
+
+fn sequence() -> impl Strategy<Value = (i16, EcLevel, Vec<u8>)> {
+    (1..=MAX_CANVAS_VERSION, 0..EC_LEVELS.len(), 1usize..4)
+        .prop_map(|(version, ec_level, chunks)| (version, EC_LEVELS[ec_level], chunks))
+        .prop_filter(
+            "manifest must fit in one frame",
+            |(version, ec_level, _)| chunk_capacity(*version, *ec_level) >= MANIFEST_SIZE,
+        )
+        .prop_flat_map(|(version, ec_level, chunks)| {
+            let capacity = chunk_capacity(version, ec_level);
+            let len = prop_oneof![
+                Just(0),
+                Just(capacity),
+                Just(capacity + 1),
+                Just(chunks * capacity),
+                Just(chunks * capacity + 1),
+                0..=chunks * capacity + 1,
+            ];
+            len.prop_flat_map(move |len| {
+                vec(any::<u8>(), len).prop_map(move |data| (version, ec_level, data))
+            })
+        })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 48,
+        failure_persistence: None,
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn canvas_round_trip_preserves_bytes((version, ec_level, data) in sequence()) {
+        let decoded = round_trip(&data, options(version, ec_level))?;
+        prop_assert_eq!(decoded, data);
+    }
+}
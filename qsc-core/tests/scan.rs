@@ -0,0 +1,147 @@
+use qsc_core::qrcode::{Color, EcLevel, Version};
+use qsc_core::{
+    Matrix, Symbol, decode_symbol, encode_byte_mode, encode_structured_append,
+    join_structured_append, rasterize_luma, scan_luma,
+};
+
+fn sample_data(len: usize, seed: u32) -> Vec<u8> {
+    (0..len as u32)
+        .map(|i| ((i * 131 + seed) % 251) as u8)
+        .collect()
+}
+
+// Resamples `source` through a rotation, scale and slight perspective tilt,
+// then adds a lighting gradient and noise, roughly what a phone camera sees.
+fn photograph(source: &[u8], size: u32, out: u32, angle: f64, scale: f64) -> Vec<u8> {
+    let (sin, cos) = angle.sin_cos();
+    let center = out as f64 / 2.0;
+    let mut noise = 12345u32;
+    let mut pixels = Vec::with_capacity((out * out) as usize);
+    for y in 0..out {
+        for x in 0..out {
+            let (u, v) = (x as f64 - center, y as f64 - center);
+            let w = scale * (1.0 + 0.0004 * u);
+            let sx = (cos * u + sin * v) / w + size as f64 / 2.0;
+            let sy = (-sin * u + cos * v) / w + size as f64 / 2.0;
+            let value = if sx < 0.0 || sy < 0.0 || sx >= size as f64 || sy >= size as f64 {
+                230.0
+            } else {
+                source[sy as usize * size as usize + sx as usize] as f64
+            };
+            noise = noise.wrapping_mul(1103515245).wrapping_add(12345);
+            let jitter = (noise >> 16) as f64 % 30.0 - 15.0;
+            let light = 0.55 + 0.4 * x as f64 / out as f64;
+            pixels.push((value * light + 20.0 + jitter).clamp(0.0, 255.0) as u8);
+        }
+    }
+    pixels
+}
+
+#[test]
+fn decodes_versions_and_levels_from_the_matrix() {
+    for version in [1, 2, 6, 7, 9, 10, 26, 27, 40] {
+        for ec_level in [EcLevel::L, EcLevel::M, EcLevel::Q, EcLevel::H] {
+            let data = sample_data(version as usize * 5, version as u32);
+            let code = encode_byte_mode(&data, Version::Normal(version), ec_level).unwrap();
+            let scanned = decode_symbol(&code).unwrap();
+            assert_eq!(scanned.data, data, "version {} {:?}", version, ec_level);
+            assert_eq!(scanned.version, Version::Normal(version));
+            assert_eq!(scanned.ec_level, ec_level);
+        }
+    }
+}
+
+#[test]
+fn corrects_damaged_modules() {
+    let data = sample_data(60, 3);
+    let code = encode_byte_mode(&data, Version::Normal(8), EcLevel::H).unwrap();
+    let width = code.width();
+    let mut colors = code.to_colors();
+    // A scratch across the bottom-right data area.
+    for i in 0..12 {
+        let index = (width - 10 + i % 3) * width + width - 20 + i;
+        colors[index] = match colors[index] {
+            Color::Dark => Color::Light,
+            Color::Light => Color::Dark,
+        };
+    }
+
+    struct Damaged(usize, Vec<Color>);
+    impl Matrix for Damaged {
+        fn width(&self) -> usize {
+            self.0
+        }
+        fn color(&self, x: usize, y: usize) -> Color {
+            self.1[y * self.0 + x]
+        }
+    }
+    assert_eq!(decode_symbol(&Damaged(width, colors)).unwrap().data, data);
+}
+
+#[test]
+fn scans_rendered_codes() {
+    for (version, canvas) in [(1, 100), (5, 200), (8, 200), (20, 500), (40, 740)] {
+        let data = sample_data(version as usize * 10, 7);
+        let code = encode_byte_mode(&data, Version::Normal(version), EcLevel::M).unwrap();
+        let pixels = rasterize_luma(&code, canvas, 4);
+        let scanned = scan_luma(&pixels, canvas, canvas);
+        assert_eq!(scanned.len(), 1, "version {}", version);
+        assert_eq!(scanned[0].data, data);
+    }
+}
+
+#[test]
+fn mismatched_dimensions_scan_nothing() {
+    let code = encode_byte_mode(b"qsc", Version::Normal(1), EcLevel::M).unwrap();
+    let pixels = rasterize_luma(&code, 100, 4);
+    assert_eq!(scan_luma(&pixels, 100, 100).len(), 1);
+    assert!(scan_luma(&pixels, 100, 99).is_empty());
+    assert!(scan_luma(&pixels[1..], 100, 100).is_empty());
+    assert!(scan_luma(&pixels, u32::MAX, u32::MAX).is_empty());
+    assert!(scan_luma(&[], 0, 100).is_empty());
+}
+
+#[test]
+fn scans_a_tilted_unevenly_lit_photo() {
+    for (version, angle) in [(3, 0.3), (8, -0.5), (15, 2.0)] {
+        let data = sample_data(40, version as u32);
+        let code = encode_byte_mode(&data, Version::Normal(version), EcLevel::M).unwrap();
+        let size = (code.width() as u32 + 8) * 6;
+        let source = rasterize_luma(&code, size, 4);
+        let photo = photograph(&source, size, size + 200, angle, 0.9);
+        let scanned = scan_luma(&photo, size + 200, size + 200);
+        assert_eq!(scanned.len(), 1, "version {}", version);
+        assert_eq!(scanned[0].data, data);
+    }
+}
+
+#[test]
+fn scans_every_code_on_a_page_and_joins_structured_append() {
+    let data = sample_data(300, 11);
+    let symbols: Vec<Symbol> =
+        encode_structured_append(&data, Version::Normal(4), EcLevel::L).unwrap();
+    assert!(symbols.len() > 3);
+
+    let cell = 180u32;
+    let columns = 3u32;
+    let rows = (symbols.len() as u32).div_ceil(columns);
+    let (width, height) = (cell * columns, cell * rows);
+    let mut page = vec![255u8; (width * height) as usize];
+    for (index, symbol) in symbols.iter().enumerate() {
+        let tile = rasterize_luma(symbol, cell, 4);
+        let (left, top) = (
+            (index as u32 % columns) * cell,
+            (index as u32 / columns) * cell,
+        );
+        for y in 0..cell {
+            let row = ((top + y) * width + left) as usize;
+            page[row..row + cell as usize]
+                .copy_from_slice(&tile[(y * cell) as usize..((y + 1) * cell) as usize]);
+        }
+    }
+
+    let scanned = scan_luma(&page, width, height);
+    assert_eq!(scanned.len(), symbols.len());
+    assert_eq!(join_structured_append(&scanned).unwrap(), data);
+    assert!(join_structured_append(&scanned[1..]).is_err());
+}
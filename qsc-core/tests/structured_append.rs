@@ -0,0 +1,55 @@
+use qsc_core::qrcode::{EcLevel, Version};
+use qsc_core::{
+    Error, MAX_STRUCTURED_APPEND_SYMBOLS, Matrix, encode_structured_append,
+    structured_append_symbol_capacity,
+};
+
+#[test]
+fn symbol_capacity_accounts_for_structured_append_header() {
+    // Version 8-L holds 194 data codewords: minus 20 header bits, 4 mode bits
+    // and an 8-bit byte count.
+    assert_eq!(
+        structured_append_symbol_capacity(Version::Normal(8), EcLevel::L).unwrap(),
+        190
+    );
+    // From version 10 the byte count grows to 16 bits.
+    assert_eq!(
+        structured_append_symbol_capacity(Version::Normal(10), EcLevel::L).unwrap(),
+        269
+    );
+}
+
+#[test]
+fn splits_data_over_the_needed_number_of_symbols() {
+    let data = vec![0x5a; 1_000];
+    let symbols = encode_structured_append(&data, Version::Normal(8), EcLevel::L).unwrap();
+
+    assert_eq!(symbols.len(), 6);
+    for symbol in &symbols {
+        assert_eq!(symbol.width(), 49);
+        assert_eq!(symbol.version(), Version::Normal(8));
+    }
+    let carried: Vec<u8> = symbols
+        .iter()
+        .flat_map(|symbol| symbol.data().to_vec())
+        .collect();
+    assert_eq!(carried, data);
+}
+
+#[test]
+fn rejects_data_needing_more_than_sixteen_symbols() {
+    let data = vec![0; 190 * MAX_STRUCTURED_APPEND_SYMBOLS + 1];
+
+    assert_eq!(
+        encode_structured_append(&data, Version::Normal(8), EcLevel::L).unwrap_err(),
+        Error::TooManySymbols {
+            needed: 17,
+            max: MAX_STRUCTURED_APPEND_SYMBOLS
+        }
+    );
+}
+
+#[test]
+fn rejects_micro_qr_versions() {
+    assert!(encode_structured_append(b"data", Version::Micro(2), EcLevel::L).is_err());
+}
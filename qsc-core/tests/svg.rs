@@ -0,0 +1,54 @@
+use qsc_core::qrcode::{EcLevel, QrCode, Version};
+use qsc_core::{Matrix, frame_file_name, render_svg, render_svg_sheet};
+
+fn dark_count(matrix: &impl Matrix) -> usize {
+    let width = matrix.width();
+    (0..width * width)
+        .filter(|i| matrix.is_dark(i % width, i / width))
+        .count()
+}
+
+// Sums the width of every `M{x} {y}h{n}v1h-{n}z` run in the path data.
+fn path_area(svg: &str) -> usize {
+    svg.split(r#" d=""#)
+        .skip(1)
+        .flat_map(|path| path.split('"').next().unwrap().split('z'))
+        .filter(|run| !run.is_empty())
+        .map(|run| {
+            let width = run.split('h').nth(1).unwrap().split('v').next().unwrap();
+            width.parse::<usize>().unwrap()
+        })
+        .sum()
+}
+
+#[test]
+fn svg_covers_exactly_the_dark_modules() {
+    let code = QrCode::with_version(b"hello", Version::Normal(2), EcLevel::M).unwrap();
+    let svg = render_svg(&code, 8, 4);
+
+    let size = code.width() + 8;
+    assert!(svg.contains(&format!(r#"viewBox="0 0 {} {}""#, size, size)));
+    assert!(svg.contains(&format!(r#"width="{}""#, size * 8)));
+    assert_eq!(path_area(&svg), dark_count(&code));
+    // Top-left finder pattern starts right after the quiet zone.
+    assert!(svg.contains("M4 4h7v1h-7z"));
+}
+
+#[test]
+fn sheet_lays_frames_out_in_a_grid() {
+    let codes: Vec<QrCode> = (0..5)
+        .map(|i| QrCode::with_version([i], Version::Normal(1), EcLevel::L).unwrap())
+        .collect();
+    let svg = render_svg_sheet(&codes, 2, 1, 3);
+
+    let cell = 21 + 2;
+    assert!(svg.contains(&format!(r#"viewBox="0 0 {} {}""#, 3 * cell, 2 * cell)));
+    assert_eq!(svg.matches("<path").count(), 5);
+    assert_eq!(path_area(&svg), codes.iter().map(dark_count).sum::<usize>());
+}
+
+#[test]
+fn frame_file_names_sort_and_count_from_one() {
+    assert_eq!(frame_file_name(0, 42, "png"), "frame-0001-of-0042.png");
+    assert_eq!(frame_file_name(41, 42, "svg"), "frame-0042-of-0042.svg");
+}
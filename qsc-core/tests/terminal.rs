@@ -0,0 +1,125 @@
+use qsc_core::qrcode::{EcLevel, QrCode, Version};
+use qsc_core::{
+    EncodeOptions, Encoder, Matrix, TerminalOptions, play_encoder, play_terminal, render_terminal,
+};
+
+fn sample_code() -> QrCode {
+    QrCode::with_version(b"terminal", Version::Normal(1), EcLevel::L).unwrap()
+}
+
+// Expands half-block output back into one bool per module, `true` for ink.
+fn decode_plain(text: &str) -> Vec<Vec<bool>> {
+    let mut rows = Vec::new();
+    for line in text.lines() {
+        let (top, bottom): (Vec<bool>, Vec<bool>) = line
+            .chars()
+            .map(|c| match c {
+                '█' => (true, true),
+                '▀' => (true, false),
+                '▄' => (false, true),
+                _ => (false, false),
+            })
+            .unzip();
+        rows.push(top);
+        rows.push(bottom);
+    }
+    rows
+}
+
+#[test]
+fn half_blocks_pack_two_module_rows_per_line() {
+    let code = sample_code();
+    let options = TerminalOptions {
+        quiet_zone: 1,
+        colors: false,
+        dark_background: false,
+    };
+    let text = render_terminal(&code, &options);
+
+    // 21 modules + 2 quiet rows = 23 rows, so 12 lines of 23 cells.
+    assert_eq!(text.lines().count(), 12);
+    assert!(text.lines().all(|line| line.chars().count() == 23));
+
+    let rows = decode_plain(&text);
+    for (y, row) in rows.iter().take(23).enumerate() {
+        for (x, &ink) in row.iter().enumerate() {
+            let dark = (1..22).contains(&x) && (1..22).contains(&y) && code.is_dark(x - 1, y - 1);
+            assert_eq!(ink, dark, "module ({}, {})", x, y);
+        }
+    }
+}
+
+#[test]
+fn dark_background_draws_light_modules() {
+    let code = sample_code();
+    let light = render_terminal(&code, &TerminalOptions::default());
+    let dark = render_terminal(
+        &code,
+        &TerminalOptions {
+            dark_background: false,
+            ..TerminalOptions::default()
+        },
+    );
+
+    let light_rows = decode_plain(&light);
+    let dark_rows = decode_plain(&dark);
+    // Every module of the symbol and its quiet zone is flipped; only the
+    // padding row below an odd-height grid stays blank in both.
+    for (light_row, dark_row) in light_rows.iter().zip(&dark_rows).take(29) {
+        for (light_ink, dark_ink) in light_row.iter().zip(dark_row) {
+            assert_ne!(light_ink, dark_ink);
+        }
+    }
+    assert!(light_rows[29].iter().all(|ink| !ink));
+}
+
+#[test]
+fn ansi_mode_paints_explicit_colours() {
+    let options = TerminalOptions {
+        colors: true,
+        ..TerminalOptions::default()
+    };
+    let text = render_terminal(&sample_code(), &options);
+
+    assert!(text.contains("\x1b[30;40m▀"));
+    assert!(text.contains("\x1b[97;107m▀"));
+    assert!(text.lines().all(|line| line.ends_with("\x1b[0m")));
+}
+
+#[test]
+fn playback_redraws_each_frame_in_place() {
+    let frames = (0..3).map(|i| (sample_code(), format!("{} / 3", i + 1)));
+    let mut out = Vec::new();
+    play_terminal(&mut out, frames, &TerminalOptions::default(), 1000).unwrap();
+
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(out.matches("\x1b[H").count(), 4);
+    for i in 1..=3 {
+        assert!(out.contains(&format!("{} / 3", i)));
+    }
+}
+
+#[test]
+fn encoder_playback_loops_with_captions() {
+    let options = EncodeOptions {
+        version: Version::Normal(5),
+        ..EncodeOptions::default()
+    };
+    let data: Vec<u8> = (0..300u32).map(|i| (i * 7 % 251) as u8).collect();
+    let encoder = Encoder::new(data, options).unwrap();
+    let total = encoder.frame_count() + 1;
+
+    let mut out = Vec::new();
+    play_encoder(
+        &mut out,
+        &encoder,
+        &TerminalOptions::default(),
+        1000,
+        Some(2),
+    )
+    .unwrap();
+
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(out.matches("\x1b[H").count(), 2 * total + 1);
+    assert_eq!(out.matches(&format!("1 / {} · ", total)).count(), 2);
+}
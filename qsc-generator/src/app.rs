@@ -1,5 +1,6 @@
 use crate::APPLICATION_NAME;
-use crate::events::setup_file_selector;
+use crate::events::{setup_export_buttons, setup_file_selector, setup_frame_actions};
+use crate::player::setup_player;
 use crate::utils::get_document;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
@@ -28,7 +29,11 @@ pub fn update_initialization_message(success: Option<bool>) -> Result<(), JsValu
 
 #[wasm_bindgen]
 pub fn init_app() -> Result<(), JsValue> {
-    if let Err(e) = setup_file_selector() {
+    if let Err(e) = setup_file_selector()
+        .and_then(|_| setup_export_buttons())
+        .and_then(|_| setup_frame_actions())
+        .and_then(|_| setup_player())
+    {
         console::error_1(&format!("Failed to initialize application: {:?}", e).into());
         update_initialization_message(Some(false))?;
     } else {
@@ -1,7 +1,6 @@
-pub const DEFAULT_QRCODE_MAX_DATA_CAPACITY: usize = 2953;
 pub const APPLICATION_NAME: &str = "Quick Sequence Code";
 pub const PERFORMANCE_THRESHOLD: usize = 50;
 pub const DEFAULT_QR_CODE_SIZE: u32 = 200;
-pub const METADATA_OVERHEAD: usize = 12;
 pub const ANIMATION_DELAY_INCREMENT: f64 = 0.1;
-pub const DEFAULT_QR_CODE_VERSION: i16 = 8;
+pub const DEFAULT_PLAYER_FPS: u32 = 5;
+pub const MAX_PLAYER_FPS: u32 = 60;
@@ -3,7 +3,7 @@ use crate::utils::get_document;
 use js_sys::Array;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
-use web_sys::{Element, HtmlCanvasElement, HtmlElement};
+use web_sys::{Element, HtmlButtonElement, HtmlCanvasElement, HtmlElement};
 
 pub fn create_canvas(
     id: &str,
@@ -28,6 +28,54 @@ pub fn create_canvas(
     Ok(canvas)
 }
 
+pub fn create_caption_element(caption: &str) -> Result<Element, JsValue> {
+    let document = get_document()?;
+    let label = document.create_element("div")?;
+    label.set_class_name("frame-caption");
+    label.set_text_content(Some(caption));
+    Ok(label)
+}
+
+// Wraps a rendered canvas with its caption and per-frame actions; `index` is
+// the frame's position in the current sequence.
+pub fn create_frame_element(
+    canvas: &HtmlCanvasElement,
+    index: usize,
+    caption: &str,
+) -> Result<Element, JsValue> {
+    let document = get_document()?;
+    let frame = document.create_element("div")?;
+    frame.set_class_name("frame");
+
+    let label = create_caption_element(caption)?;
+
+    let download = document.create_element("button")?;
+    download.set_class_name("frame-download");
+    download.set_attribute("data-index", &index.to_string())?;
+    download.set_attribute("title", "Download this frame as SVG")?;
+    download.set_text_content(Some("SVG"));
+
+    frame.append_child(canvas)?;
+    frame.append_child(&label)?;
+    frame.append_child(&download)?;
+    Ok(frame)
+}
+
+pub fn set_exports_enabled(enabled: bool) -> Result<(), JsValue> {
+    let document = get_document()?;
+    let buttons = document.query_selector_all(".export-container button")?;
+
+    for i in 0..buttons.length() {
+        if let Some(button) = buttons.item(i)
+            && let Ok(button) = button.dyn_into::<HtmlButtonElement>()
+        {
+            button.set_disabled(!enabled);
+        }
+    }
+
+    Ok(())
+}
+
 pub fn create_status_message(text: &str, is_error: Option<bool>) -> Result<HtmlElement, JsValue> {
     let document = get_document()?;
     let div = document.create_element("div")?.dyn_into::<HtmlElement>()?;
@@ -0,0 +1,38 @@
+use crate::utils::get_document;
+use js_sys::{Array, Uint8Array};
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url, window};
+
+const REVOKE_DELAY_MS: i32 = 1000;
+
+pub fn download_bytes(file_name: &str, bytes: &[u8], mime_type: &str) -> Result<(), JsValue> {
+    let parts = Array::new();
+    parts.push(&Uint8Array::from(bytes));
+
+    let properties = BlobPropertyBag::new();
+    properties.set_type(mime_type);
+    let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &properties)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let document = get_document()?;
+    let anchor = document
+        .create_element("a")?
+        .dyn_into::<HtmlAnchorElement>()?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    // The browser starts the download after click() returns, so the URL has
+    // to outlive this call; revoke it on a later tick.
+    let revoke = Closure::once_into_js(move || {
+        let _ = Url::revoke_object_url(&url);
+    });
+    window()
+        .ok_or("No global `window` exists")?
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            revoke.unchecked_ref(),
+            REVOKE_DELAY_MS,
+        )?;
+    Ok(())
+}
@@ -1,12 +1,16 @@
 use crate::dom::show_error_state;
+use crate::export::{
+    download_frame_svg, download_sequence_apng, download_sequence_gif, download_sequence_pdf,
+    download_sequence_svg, download_sequence_zip,
+};
 use crate::file_handler::handle_file_change;
+use crate::player::{close_player, open_player};
+use crate::utils::get_document;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
-use web_sys::{Event, HtmlInputElement, console};
+use web_sys::{Element, Event, HtmlInputElement, console};
 
 pub fn setup_file_selector() -> Result<(), JsValue> {
-    use crate::utils::get_document;
-
     let document = get_document()?;
     let file_selector = document
         .get_element_by_id("file-selector")
@@ -41,3 +45,64 @@ pub fn setup_file_selector() -> Result<(), JsValue> {
 
     Ok(())
 }
+
+fn on_click(element_id: &'static str, action: fn() -> Result<(), JsValue>) -> Result<(), JsValue> {
+    let document = get_document()?;
+    let element = document
+        .get_element_by_id(element_id)
+        .ok_or_else(|| JsValue::from_str(&format!("Element with id '{}' not found", element_id)))?;
+
+    let closure = Closure::wrap(Box::new(move |_event: Event| {
+        if let Err(e) = action() {
+            console::error_1(&format!("Action on '{}' failed: {:?}", element_id, e).into());
+        }
+    }) as Box<dyn FnMut(Event)>);
+
+    element.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+    closure.forget();
+
+    Ok(())
+}
+
+pub fn setup_export_buttons() -> Result<(), JsValue> {
+    on_click("download-zip", download_sequence_zip)?;
+    on_click("download-svg", download_sequence_svg)?;
+    on_click("download-gif", download_sequence_gif)?;
+    on_click("download-apng", download_sequence_apng)?;
+    on_click("download-pdf", download_sequence_pdf)?;
+    on_click("play-sequence", open_player)?;
+    on_click("player-close", close_player)
+}
+
+// Frame buttons come and go with every sequence, so one delegated listener on
+// the container handles all of them.
+pub fn setup_frame_actions() -> Result<(), JsValue> {
+    let document = get_document()?;
+    let container = document
+        .get_element_by_id("canvas-container")
+        .ok_or("Canvas container not found")?;
+
+    let closure = Closure::wrap(Box::new(move |event: Event| {
+        let Some(button) = event
+            .target()
+            .and_then(|target| target.dyn_into::<Element>().ok())
+            .filter(|element| element.class_list().contains("frame-download"))
+        else {
+            return;
+        };
+
+        let index = button
+            .get_attribute("data-index")
+            .and_then(|index| index.parse().ok());
+        if let Some(index) = index
+            && let Err(e) = download_frame_svg(index)
+        {
+            console::error_1(&format!("Failed to download frame {}: {:?}", index, e).into());
+        }
+    }) as Box<dyn FnMut(Event)>);
+
+    container.add_event_listener_with_callback("click", closure.as_ref().unchecked_ref())?;
+    closure.forget();
+
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use crate::download::download_bytes;
+use crate::options::{read_animation_options, read_paper_options};
+use crate::sequence::{Sequence, current_sequence};
+use qsc_core::{
+    AnimationFormat, Matrix, PaperFrame, build_png_zip, frame_checksum, frame_file_name,
+    module_pixels, render_animation, render_pdf, render_svg, render_svg_sheet,
+};
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use web_sys::console;
+
+const SVG_MIME_TYPE: &str = "image/svg+xml";
+const ZIP_MIME_TYPE: &str = "application/zip";
+const PDF_MIME_TYPE: &str = "application/pdf";
+
+fn require_sequence() -> Result<Rc<Sequence>, JsValue> {
+    current_sequence().ok_or_else(|| JsValue::from_str("No sequence generated yet"))
+}
+
+fn to_js_error(error: qsc_core::Error) -> JsValue {
+    JsValue::from_str(&error.to_string())
+}
+
+// Same pixels per module as the on-screen canvas, so the default size matches.
+fn export_module_size(sequence: &Sequence, code: &impl Matrix) -> u32 {
+    let options = sequence.render_options;
+    module_pixels(code.width(), options.canvas_size, options.quiet_zone).max(1)
+}
+
+pub fn download_frame_svg(index: usize) -> Result<(), JsValue> {
+    let sequence = require_sequence()?;
+    if index >= sequence.len() {
+        return Err(JsValue::from_str(&format!("Frame {} out of range", index)));
+    }
+
+    let code = sequence.code(index).map_err(to_js_error)?;
+    let svg = render_svg(
+        &code,
+        export_module_size(&sequence, &code),
+        sequence.render_options.quiet_zone,
+    );
+    let file_name = frame_file_name(index, sequence.len(), "svg");
+    console::log_1(&format!("Downloading {}", file_name).into());
+
+    download_bytes(&file_name, svg.as_bytes(), SVG_MIME_TYPE)
+}
+
+pub fn download_sequence_svg() -> Result<(), JsValue> {
+    let sequence = require_sequence()?;
+    let codes = sequence.codes().map_err(to_js_error)?;
+    let Some(first) = codes.first() else {
+        return Err(JsValue::from_str("Sequence is empty"));
+    };
+
+    let columns = (codes.len() as f64).sqrt().ceil() as usize;
+    let svg = render_svg_sheet(
+        &codes,
+        export_module_size(&sequence, first),
+        sequence.render_options.quiet_zone,
+        columns,
+    );
+    let file_name = format!("{}-frames.svg", sequence.file_name);
+    console::log_1(&format!("Downloading {} ({} frames)", file_name, codes.len()).into());
+
+    download_bytes(&file_name, svg.as_bytes(), SVG_MIME_TYPE)
+}
+
+pub fn download_sequence_zip() -> Result<(), JsValue> {
+    let sequence = require_sequence()?;
+    let codes = sequence.codes().map_err(to_js_error)?;
+    let options = sequence.render_options;
+
+    let zip =
+        build_png_zip(&codes, options.canvas_size, options.quiet_zone).map_err(to_js_error)?;
+    let file_name = format!("{}-frames.zip", sequence.file_name);
+    console::log_1(
+        &format!(
+            "Downloading {} ({} frames, {} bytes)",
+            file_name,
+            codes.len(),
+            zip.len()
+        )
+        .into(),
+    );
+
+    download_bytes(&file_name, &zip, ZIP_MIME_TYPE)
+}
+
+pub fn download_sequence_animation(format: AnimationFormat) -> Result<(), JsValue> {
+    let sequence = require_sequence()?;
+    let codes = sequence.codes().map_err(to_js_error)?;
+    let options = read_animation_options(format, sequence.render_options.quiet_zone)?;
+
+    let animation = render_animation(&codes, &options).map_err(to_js_error)?;
+    let file_name = format!("{}-frames.{}", sequence.file_name, format.extension());
+    console::log_1(
+        &format!(
+            "Downloading {} ({} frames, {} ms per frame, {} bytes)",
+            file_name,
+            codes.len(),
+            options.frame_delay_ms,
+            animation.len()
+        )
+        .into(),
+    );
+
+    download_bytes(&file_name, &animation, format.mime_type())
+}
+
+pub fn download_sequence_gif() -> Result<(), JsValue> {
+    download_sequence_animation(AnimationFormat::Gif)
+}
+
+pub fn download_sequence_apng() -> Result<(), JsValue> {
+    download_sequence_animation(AnimationFormat::Apng)
+}
+
+pub fn download_sequence_pdf() -> Result<(), JsValue> {
+    let sequence = require_sequence()?;
+    let options = read_paper_options(sequence.render_options.quiet_zone)?;
+    let frames: Vec<PaperFrame<Box<dyn Matrix>>> = sequence
+        .frames()
+        .map_err(to_js_error)?
+        .into_iter()
+        .map(|frame| PaperFrame {
+            checksum: frame_checksum(&frame.payload),
+            code: frame.code,
+        })
+        .collect();
+
+    let pdf = render_pdf(&sequence.file_name, &frames, &options).map_err(to_js_error)?;
+    let file_name = format!("{}-backup.pdf", sequence.file_name);
+    console::log_1(
+        &format!(
+            "Downloading {} ({} frames, {:?}, {} bytes)",
+            file_name,
+            frames.len(),
+            options.page_size,
+            pdf.len()
+        )
+        .into(),
+    );
+
+    download_bytes(&file_name, &pdf, PDF_MIME_TYPE)
+}
@@ -1,8 +1,17 @@
 use crate::dom::show_error_state;
-use crate::dom::{clear_container, create_canvas, show_loading_state};
-use crate::qrcode::{get_max_qr_capacity, render_qrcode_canvas};
+use crate::dom::{
+    clear_container, create_canvas, create_frame_element, set_exports_enabled, show_loading_state,
+};
+use crate::options::{
+    OutputFormat, RenderOptions, read_encode_options, read_output_format, read_render_options,
+};
+use crate::qrcode::render_qrcode_canvas;
+use crate::sequence::{Sequence, SequenceSource, set_current_sequence};
 use crate::utils::get_document;
 use js_sys::{ArrayBuffer, Uint8Array};
+use qsc_core::{
+    EncodeOptions, Encoder, check_canvas_size, encode_structured_append, frame_caption, to_hex,
+};
 use std::sync::Arc;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
@@ -12,29 +21,52 @@ pub fn log_file_info(file_name: &str, file_size: usize) {
     console::log_1(&format!("Processing file: {} ({} bytes)", file_name, file_size).into());
 }
 
-pub fn create_canvas_sequence(data: &[u8]) -> Result<(), JsValue> {
-    let file_size = data.len();
-    let max_capacity = get_max_qr_capacity();
-    console::log_1(&format!("Detected max QR capacity: {} bytes", max_capacity).into());
+pub fn create_canvas_sequence(
+    file_name: &str,
+    data: &[u8],
+    options: EncodeOptions,
+    render_options: RenderOptions,
+) -> Result<(), JsValue> {
+    let encoder = Encoder::new(data, options).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    console::log_1(&format!("Chunk capacity: {} bytes", encoder.chunk_capacity()).into());
+    console::log_1(
+        &format!(
+            "Compression: {:?} ({} -> {} bytes)",
+            encoder.compression(),
+            data.len(),
+            encoder.stream_len()
+        )
+        .into(),
+    );
 
-    let canvas_count = file_size.div_ceil(max_capacity);
+    let canvas_count = encoder.frame_count() + 1;
     console::log_1(&format!("canvas_count: {}", canvas_count).into());
+    console::log_1(&format!("session_id: {:08x}", encoder.session_id()).into());
+    console::log_1(&format!("encrypted: {}", encoder.is_encrypted()).into());
+    console::log_1(&format!("sha256: {}", to_hex(&encoder.manifest().sha256)).into());
 
     clear_container("canvas-container")?;
 
-    let data_arc = Arc::new(data.to_vec());
+    let encoder = Arc::new(encoder);
+    set_current_sequence(Some(Sequence {
+        file_name: file_name.to_string(),
+        source: SequenceSource::Frames(Arc::clone(&encoder)),
+        render_options,
+    }));
+    set_exports_enabled(true)?;
 
-    create_canvas_async(0, canvas_count, data_arc, max_capacity)?;
+    create_canvas_async(0, encoder, render_options)?;
 
     Ok(())
 }
 
+// Canvas 0 carries the manifest, followed by the data frames.
 fn create_canvas_async(
     index: usize,
-    total: usize,
-    data: Arc<Vec<u8>>,
-    max_capacity: usize,
+    encoder: Arc<Encoder>,
+    render_options: RenderOptions,
 ) -> Result<(), JsValue> {
+    let total = encoder.frame_count() + 1;
     if index >= total {
         console::log_1(&"All canvas elements created and rendered".into());
         return Ok(());
@@ -48,33 +80,39 @@ fn create_canvas_async(
     let canvas_id = format!("canvas-{}", index);
     console::log_1(&format!("Creating canvas with ID: {}", canvas_id).into());
 
-    let canvas = create_canvas(&canvas_id, None, None, None)?;
+    let canvas = create_canvas(
+        &canvas_id,
+        Some(render_options.canvas_size),
+        Some(render_options.canvas_size),
+        None,
+    )?;
     console::log_1(&format!("Canvas created successfully: {}", canvas_id).into());
 
-    container.append_child(&canvas)?;
+    let frame = match index {
+        0 => encoder.manifest_frame(),
+        _ => encoder.frame(index - 1),
+    };
+    let caption = match &frame {
+        Ok(frame) => frame_caption(index, total, &frame.payload),
+        Err(_) => format!("{} / {}", index + 1, total),
+    };
+
+    let element = create_frame_element(&canvas, index, &caption)?;
+    container.append_child(&element)?;
     console::log_1(&format!("Canvas appended to DOM: {}", canvas_id).into());
 
-    let chunk_start = index * max_capacity;
-    let chunk_end = ((index + 1) * max_capacity).min(data.len());
-    let mut chunk_data = &data[chunk_start..chunk_end];
-
-    if chunk_data.len() > max_capacity {
-        chunk_data = &chunk_data[..max_capacity];
-    }
-
-    console::log_1(
-        &format!(
-            "Canvas {}: chunk_start={}, chunk_end={}, chunk_size={}, max_capacity={}",
-            index,
-            chunk_start,
-            chunk_end,
-            chunk_data.len(),
-            max_capacity
-        )
-        .into(),
-    );
-
-    match render_qrcode_canvas(&canvas_id, chunk_data) {
+    match frame
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+        .and_then(|frame| {
+            console::log_1(
+                &format!(
+                    "Canvas {}: frame {}/{}, payload_len={}",
+                    index, frame.header.index, frame.header.total, frame.header.payload_len
+                )
+                .into(),
+            );
+            render_qrcode_canvas(&canvas_id, &frame.code, render_options.quiet_zone)
+        }) {
         Ok(_) => {
             console::log_1(&format!("Canvas {} rendered successfully", index).into());
         }
@@ -85,15 +123,10 @@ fn create_canvas_async(
 
     console::log_1(&format!("Setting timeout for next canvas {}", index + 1).into());
 
-    let data_for_closure = Arc::clone(&data);
+    let encoder_for_closure = Arc::clone(&encoder);
     let closure = Closure::wrap(Box::new(move || {
         console::log_1(&format!("Timeout triggered for canvas {}", index + 1).into());
-        match create_canvas_async(
-            index + 1,
-            total,
-            Arc::clone(&data_for_closure),
-            max_capacity,
-        ) {
+        match create_canvas_async(index + 1, Arc::clone(&encoder_for_closure), render_options) {
             Ok(_) => {
                 console::log_1(&format!("Successfully processed canvas {}", index + 1).into());
             }
@@ -120,13 +153,75 @@ fn create_canvas_async(
     Ok(())
 }
 
+pub fn create_structured_append_sequence(
+    file_name: &str,
+    data: &[u8],
+    options: &EncodeOptions,
+    render_options: RenderOptions,
+) -> Result<(), JsValue> {
+    let symbols = match encode_structured_append(data, options.version, options.ec_level) {
+        Ok(symbols) => symbols,
+        Err(e) => {
+            console::error_1(&format!("Structured append failed: {}", e).into());
+            return show_error_state(Some(e.to_string().to_uppercase()));
+        }
+    };
+    console::log_1(&format!("Structured append symbols: {}", symbols.len()).into());
+
+    let container = clear_container("canvas-container")?;
+
+    for (index, symbol) in symbols.iter().enumerate() {
+        let canvas_id = format!("canvas-{}", index);
+        let canvas = create_canvas(
+            &canvas_id,
+            Some(render_options.canvas_size),
+            Some(render_options.canvas_size),
+            None,
+        )?;
+        let caption = frame_caption(index, symbols.len(), symbol.data());
+        let frame = create_frame_element(&canvas, index, &caption)?;
+        container.append_child(&frame)?;
+        render_qrcode_canvas(&canvas_id, symbol, render_options.quiet_zone)?;
+    }
+
+    set_current_sequence(Some(Sequence {
+        file_name: file_name.to_string(),
+        source: SequenceSource::StructuredAppend(symbols),
+        render_options,
+    }));
+    set_exports_enabled(true)?;
+
+    Ok(())
+}
+
 pub fn process_selected_file(file_name: &str, data: &[u8]) -> Result<(), JsValue> {
     log_file_info(file_name, data.len());
 
     console::log_1(&data.len().to_string().into());
 
+    let options = read_encode_options()?;
+    let format = read_output_format()?;
+    let render_options = read_render_options()?;
+
+    set_current_sequence(None);
+    set_exports_enabled(false)?;
+
+    if let Err(e) = check_canvas_size(
+        options.version,
+        render_options.canvas_size,
+        render_options.quiet_zone,
+    ) {
+        console::error_1(&format!("Invalid render options: {}", e).into());
+        return show_error_state(Some(e.to_string().to_uppercase()));
+    }
+
     show_loading_state()?;
-    create_canvas_sequence(data)?;
+    match format {
+        OutputFormat::Frames => create_canvas_sequence(file_name, data, options, render_options)?,
+        OutputFormat::StructuredAppend => {
+            create_structured_append_sequence(file_name, data, &options, render_options)?
+        }
+    }
 
     console::log_1(&"QR codes generated successfully".into());
     Ok(())
@@ -3,22 +3,41 @@ use wasm_bindgen::prelude::*;
 mod app;
 mod constants;
 mod dom;
+mod download;
 mod events;
+mod export;
 mod file_handler;
+mod options;
+mod player;
 mod qrcode;
+mod sequence;
 mod utils;
 
 pub use app::{init_app, update_initialization_message};
 pub use constants::*;
 pub use dom::{
     add_qrcode_elements_to_dom, append_elements_to_container, append_elements_with_inner_html,
-    clear_container, create_canvas, create_error_message, create_loading_message,
-    create_qrcode_elements, create_qrcode_elements_as_html, create_status_message,
-    show_error_state, show_loading_state,
+    clear_container, create_canvas, create_caption_element, create_error_message,
+    create_frame_element, create_loading_message, create_qrcode_elements,
+    create_qrcode_elements_as_html, create_status_message, set_exports_enabled, show_error_state,
+    show_loading_state,
+};
+pub use download::download_bytes;
+pub use events::{setup_export_buttons, setup_file_selector, setup_frame_actions};
+pub use export::{
+    download_frame_svg, download_sequence_animation, download_sequence_apng, download_sequence_gif,
+    download_sequence_pdf, download_sequence_svg, download_sequence_zip,
 };
-pub use events::setup_file_selector;
 pub use file_handler::{handle_file_change, log_file_info, process_selected_file};
+pub use options::{
+    get_input_value, get_number_value, read_animation_options, read_encode_options,
+    read_paper_options, read_render_options,
+};
+pub use player::{close_player, open_player, setup_player};
 pub use qrcode::render_qrcode_canvas;
+pub use sequence::{
+    Sequence, SequenceFrame, SequenceSource, current_sequence, set_current_sequence,
+};
 
 #[wasm_bindgen(start)]
 pub fn main() {
@@ -0,0 +1,169 @@
+use crate::constants::DEFAULT_QR_CODE_SIZE;
+use crate::utils::get_document;
+use qsc_core::qrcode::Version;
+use qsc_core::{
+    AnimationFormat, AnimationOptions, DEFAULT_QUIET_ZONE, EncodeOptions, MAX_ANIMATION_SCALE,
+    MAX_PAPER_COLUMNS, MAX_QUIET_ZONE, PageSize, PaperOptions, TransferMode, parse_ec_level,
+};
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Frames,
+    StructuredAppend,
+}
+
+pub fn get_input_value(input_id: &str) -> Result<String, JsValue> {
+    let document = get_document()?;
+    let input = document
+        .get_element_by_id(input_id)
+        .ok_or_else(|| JsValue::from_str(&format!("Input with id '{}' not found", input_id)))?
+        .dyn_into::<HtmlInputElement>()?;
+
+    Ok(input.value())
+}
+
+pub fn get_select_value(select_id: &str) -> Result<String, JsValue> {
+    let document = get_document()?;
+    let select = document
+        .get_element_by_id(select_id)
+        .ok_or_else(|| JsValue::from_str(&format!("Select with id '{}' not found", select_id)))?
+        .dyn_into::<HtmlSelectElement>()?;
+
+    Ok(select.value())
+}
+
+pub fn read_output_format() -> Result<OutputFormat, JsValue> {
+    match get_select_value("output-format")?.as_str() {
+        "frames" => Ok(OutputFormat::Frames),
+        "structured-append" => Ok(OutputFormat::StructuredAppend),
+        other => Err(JsValue::from_str(&format!(
+            "Unknown output format '{}'",
+            other
+        ))),
+    }
+}
+
+pub fn read_transfer_mode() -> Result<TransferMode, JsValue> {
+    match get_select_value("transfer-mode")?.as_str() {
+        "chunked" => Ok(TransferMode::Chunked),
+        "fountain" => Ok(TransferMode::Fountain),
+        other => Err(JsValue::from_str(&format!(
+            "Unknown transfer mode '{}'",
+            other
+        ))),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    pub canvas_size: u32,
+    pub quiet_zone: u32,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            canvas_size: DEFAULT_QR_CODE_SIZE,
+            quiet_zone: DEFAULT_QUIET_ZONE,
+        }
+    }
+}
+
+pub fn get_number_value(input_id: &str, default: u32) -> Result<u32, JsValue> {
+    let value = get_input_value(input_id)?;
+    if value.is_empty() {
+        return Ok(default);
+    }
+
+    value
+        .parse()
+        .map_err(|_| JsValue::from_str(&format!("Invalid value '{}' for '{}'", value, input_id)))
+}
+
+pub fn read_render_options() -> Result<RenderOptions, JsValue> {
+    let defaults = RenderOptions::default();
+
+    Ok(RenderOptions {
+        canvas_size: get_number_value("canvas-size", defaults.canvas_size)?,
+        quiet_zone: check_quiet_zone(get_number_value("quiet-zone", defaults.quiet_zone)?)?,
+    })
+}
+
+fn check_quiet_zone(quiet_zone: u32) -> Result<u32, JsValue> {
+    if quiet_zone > MAX_QUIET_ZONE {
+        return Err(JsValue::from_str(&format!(
+            "Quiet zone must be between 0 and {} modules",
+            MAX_QUIET_ZONE
+        )));
+    }
+    Ok(quiet_zone)
+}
+
+pub fn read_encode_options() -> Result<EncodeOptions, JsValue> {
+    let version = get_select_value("qr-version")?;
+    let version = version
+        .parse()
+        .map_err(|_| JsValue::from_str(&format!("Invalid QR version '{}'", version)))?;
+    let ec_level = get_select_value("ec-level")?;
+    let ec_level = parse_ec_level(&ec_level)
+        .ok_or_else(|| JsValue::from_str(&format!("Invalid EC level '{}'", ec_level)))?;
+    let passphrase = get_input_value("passphrase")?;
+
+    Ok(EncodeOptions {
+        version: Version::Normal(version),
+        ec_level,
+        mode: read_transfer_mode()?,
+        passphrase: (!passphrase.is_empty()).then_some(passphrase),
+        ..EncodeOptions::default()
+    })
+}
+
+pub fn read_animation_options(
+    format: AnimationFormat,
+    quiet_zone: u32,
+) -> Result<AnimationOptions, JsValue> {
+    let defaults = AnimationOptions::default();
+
+    let scale = get_number_value("animation-scale", defaults.scale)?;
+    if !(1..=MAX_ANIMATION_SCALE).contains(&scale) {
+        return Err(JsValue::from_str(&format!(
+            "Animation scale must be between 1 and {} px per module",
+            MAX_ANIMATION_SCALE
+        )));
+    }
+
+    Ok(AnimationOptions {
+        format,
+        frame_delay_ms: get_number_value("frame-delay", defaults.frame_delay_ms)?,
+        scale,
+        quiet_zone: check_quiet_zone(quiet_zone)?,
+    })
+}
+
+pub fn read_paper_options(quiet_zone: u32) -> Result<PaperOptions, JsValue> {
+    let defaults = PaperOptions::default();
+    let page_size = match get_select_value("page-size")?.as_str() {
+        "a4" => PageSize::A4,
+        "letter" => PageSize::Letter,
+        other => {
+            return Err(JsValue::from_str(&format!("Unknown page size '{}'", other)));
+        }
+    };
+
+    let columns = get_number_value("paper-columns", defaults.columns as u32)? as usize;
+    if !(1..=MAX_PAPER_COLUMNS).contains(&columns) {
+        return Err(JsValue::from_str(&format!(
+            "Codes per row must be between 1 and {}",
+            MAX_PAPER_COLUMNS
+        )));
+    }
+
+    Ok(PaperOptions {
+        page_size,
+        columns,
+        quiet_zone,
+    })
+}
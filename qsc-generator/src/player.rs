@@ -0,0 +1,282 @@
+use crate::constants::{DEFAULT_PLAYER_FPS, MAX_PLAYER_FPS};
+use crate::options::get_number_value;
+use crate::qrcode::render_qrcode_canvas;
+use crate::sequence::{Sequence, current_sequence};
+use crate::utils::get_document;
+use qsc_core::frame_caption;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use web_sys::{HtmlCanvasElement, KeyboardEvent, console, window};
+
+const PLAYER_ID: &str = "player";
+const PLAYER_CANVAS_ID: &str = "player-canvas";
+const PLAYER_STATUS_ID: &str = "player-status";
+// Share of the shorter screen side given to the code.
+const PLAYER_FILL: f64 = 0.8;
+
+struct Player {
+    sequence: Rc<Sequence>,
+    position: usize,
+    fps: u32,
+    playing: bool,
+    interval: Option<i32>,
+    jump: String,
+}
+
+thread_local! {
+    static PLAYER: RefCell<Option<Player>> = const { RefCell::new(None) };
+    static TICK: RefCell<Option<Closure<dyn FnMut()>>> = const { RefCell::new(None) };
+}
+
+fn with_player<T>(f: impl FnOnce(&mut Player) -> T) -> Option<T> {
+    PLAYER.with(|player| player.borrow_mut().as_mut().map(f))
+}
+
+fn resize_canvas(canvas: &HtmlCanvasElement) -> Result<(), JsValue> {
+    let window = window().ok_or("No global `window` exists")?;
+    let width = window.inner_width()?.as_f64().unwrap_or_default();
+    let height = window.inner_height()?.as_f64().unwrap_or_default();
+    let size = (width.min(height) * PLAYER_FILL) as u32;
+
+    if canvas.width() != size {
+        canvas.set_width(size);
+        canvas.set_height(size);
+    }
+    Ok(())
+}
+
+fn status_text(player: &Player, payload: &[u8]) -> String {
+    let len = player.sequence.len();
+    let mut status = frame_caption(player.position % len, len, payload);
+    if player.position >= len {
+        status.push_str(&format!(" (PASS {})", player.position / len + 1));
+    }
+    status.push_str(&format!(" · {} FPS", player.fps));
+    status.push_str(if player.playing {
+        " · PLAYING"
+    } else {
+        " · PAUSED"
+    });
+    if !player.jump.is_empty() {
+        status.push_str(&format!(" · JUMP TO {}_", player.jump));
+    }
+    status
+}
+
+fn render_current() -> Result<(), JsValue> {
+    let Some((sequence, position)) =
+        with_player(|player| (Rc::clone(&player.sequence), player.position))
+    else {
+        return Ok(());
+    };
+
+    let document = get_document()?;
+    let canvas = document
+        .get_element_by_id(PLAYER_CANVAS_ID)
+        .ok_or("Player canvas not found")?
+        .dyn_into::<HtmlCanvasElement>()?;
+    resize_canvas(&canvas)?;
+
+    let frame = sequence
+        .playback_frame(position)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    render_qrcode_canvas(
+        PLAYER_CANVAS_ID,
+        &frame.code,
+        sequence.render_options.quiet_zone,
+    )?;
+
+    let status = with_player(|player| status_text(player, &frame.payload)).unwrap_or_default();
+    if let Some(element) = document.get_element_by_id(PLAYER_STATUS_ID) {
+        element.set_text_content(Some(&status));
+    }
+    Ok(())
+}
+
+fn stop_interval(player: &mut Player) -> Result<(), JsValue> {
+    if let Some(handle) = player.interval.take() {
+        window()
+            .ok_or("No global `window` exists")?
+            .clear_interval_with_handle(handle);
+    }
+    Ok(())
+}
+
+fn start_interval(player: &mut Player) -> Result<(), JsValue> {
+    stop_interval(player)?;
+    let window = window().ok_or("No global `window` exists")?;
+    let delay = (1000 / player.fps.max(1)) as i32;
+
+    let handle = TICK.with(|tick| {
+        let tick = tick.borrow();
+        let tick = tick.as_ref().ok_or("Player is not set up")?;
+        window.set_interval_with_callback_and_timeout_and_arguments_0(
+            tick.as_ref().unchecked_ref(),
+            delay,
+        )
+    })?;
+    player.interval = Some(handle);
+    Ok(())
+}
+
+fn set_playing(player: &mut Player, playing: bool) -> Result<(), JsValue> {
+    player.playing = playing;
+    if playing {
+        start_interval(player)
+    } else {
+        stop_interval(player)
+    }
+}
+
+fn tick() {
+    with_player(|player| player.position += 1);
+    if let Err(e) = render_current() {
+        console::error_1(&format!("Player failed to render: {:?}", e).into());
+    }
+}
+
+pub fn open_player() -> Result<(), JsValue> {
+    close_player()?;
+
+    let sequence = current_sequence().ok_or("No sequence generated yet")?;
+    let fps = get_number_value("player-fps", DEFAULT_PLAYER_FPS)?.clamp(1, MAX_PLAYER_FPS);
+    let mut player = Player {
+        sequence,
+        position: 0,
+        fps,
+        playing: true,
+        interval: None,
+        jump: String::new(),
+    };
+    start_interval(&mut player)?;
+    PLAYER.with(|current| *current.borrow_mut() = Some(player));
+
+    let document = get_document()?;
+    let element = document
+        .get_element_by_id(PLAYER_ID)
+        .ok_or("Player element not found")?;
+    element.class_list().add_1("open")?;
+    // Browsers may refuse full screen; the overlay still covers the page.
+    if let Err(e) = element.request_fullscreen() {
+        console::warn_1(&format!("Full screen unavailable: {:?}", e).into());
+    }
+
+    console::log_1(&format!("Player started at {} FPS", fps).into());
+    render_current()
+}
+
+pub fn close_player() -> Result<(), JsValue> {
+    let Some(mut player) = PLAYER.with(|current| current.borrow_mut().take()) else {
+        return Ok(());
+    };
+    stop_interval(&mut player)?;
+
+    let document = get_document()?;
+    if let Some(element) = document.get_element_by_id(PLAYER_ID) {
+        element.class_list().remove_1("open")?;
+    }
+    if document.fullscreen_element().is_some() {
+        document.exit_fullscreen();
+    }
+    Ok(())
+}
+
+// Space pauses, arrows step, Home/End jump to the ends, typed digits + Enter
+// jump to a frame, +/- change the frame rate and Escape closes the player.
+fn handle_key(event: &KeyboardEvent) -> Result<(), JsValue> {
+    let key = event.key();
+    if key == "Escape" {
+        return close_player();
+    }
+
+    let handled = with_player(|player| -> Result<bool, JsValue> {
+        let len = player.sequence.len();
+        match key.as_str() {
+            " " => set_playing(player, !player.playing)?,
+            "ArrowRight" => {
+                set_playing(player, false)?;
+                player.position += 1;
+            }
+            "ArrowLeft" => {
+                set_playing(player, false)?;
+                player.position = player.position.saturating_sub(1);
+            }
+            "Home" => player.position = 0,
+            // Stay in the current pass; fountain droplets differ from pass to pass.
+            "End" => player.position = player.position / len * len + len - 1,
+            "+" | "=" => {
+                player.fps = (player.fps + 1).min(MAX_PLAYER_FPS);
+                if player.playing {
+                    start_interval(player)?;
+                }
+            }
+            "-" => {
+                player.fps = player.fps.saturating_sub(1).max(1);
+                if player.playing {
+                    start_interval(player)?;
+                }
+            }
+            "Backspace" => {
+                player.jump.pop();
+            }
+            "Enter" => {
+                if let Ok(frame) = player.jump.parse::<usize>()
+                    && (1..=len).contains(&frame)
+                {
+                    set_playing(player, false)?;
+                    player.position = frame - 1;
+                }
+                player.jump.clear();
+            }
+            digit if digit.len() == 1 && digit.chars().all(|c| c.is_ascii_digit()) => {
+                player.jump.push_str(digit);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    });
+
+    match handled {
+        Some(Ok(true)) => {
+            event.prevent_default();
+            render_current()
+        }
+        Some(Err(e)) => Err(e),
+        _ => Ok(()),
+    }
+}
+
+pub fn setup_player() -> Result<(), JsValue> {
+    TICK.with(|current| {
+        *current.borrow_mut() = Some(Closure::wrap(Box::new(tick) as Box<dyn FnMut()>));
+    });
+
+    let document = get_document()?;
+    let closure = Closure::wrap(Box::new(move |event: KeyboardEvent| {
+        if let Err(e) = handle_key(&event) {
+            console::error_1(&format!("Player key handling failed: {:?}", e).into());
+        }
+    }) as Box<dyn FnMut(KeyboardEvent)>);
+
+    document.add_event_listener_with_callback("keydown", closure.as_ref().unchecked_ref())?;
+    closure.forget();
+
+    // Leaving full screen (Escape is usually swallowed by the browser there)
+    // closes the player too.
+    let closure = Closure::wrap(Box::new(move || {
+        let still_fullscreen = get_document()
+            .map(|document| document.fullscreen_element().is_some())
+            .unwrap_or(false);
+        if !still_fullscreen && let Err(e) = close_player() {
+            console::error_1(&format!("Failed to close player: {:?}", e).into());
+        }
+    }) as Box<dyn FnMut()>);
+
+    document
+        .add_event_listener_with_callback("fullscreenchange", closure.as_ref().unchecked_ref())?;
+    closure.forget();
+
+    Ok(())
+}
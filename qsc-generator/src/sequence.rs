@@ -0,0 +1,102 @@
+use crate::options::RenderOptions;
+use qsc_core::{Encoder, Error, Matrix, Symbol, TransferMode};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+pub enum SequenceSource {
+    Frames(Arc<Encoder>),
+    StructuredAppend(Vec<Symbol>),
+}
+
+// The last generated sequence, kept so exports can rebuild any code on demand.
+pub struct Sequence {
+    pub file_name: String,
+    pub source: SequenceSource,
+    pub render_options: RenderOptions,
+}
+
+pub struct SequenceFrame {
+    pub code: Box<dyn Matrix>,
+    pub payload: Vec<u8>,
+}
+
+impl Sequence {
+    // Frames mode shows the manifest first, followed by the data frames.
+    pub fn len(&self) -> usize {
+        match &self.source {
+            SequenceSource::Frames(encoder) => encoder.frame_count() + 1,
+            SequenceSource::StructuredAppend(symbols) => symbols.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn frame(&self, index: usize) -> Result<SequenceFrame, Error> {
+        match &self.source {
+            SequenceSource::Frames(encoder) => {
+                let frame = match index {
+                    0 => encoder.manifest_frame()?,
+                    _ => encoder.frame(index - 1)?,
+                };
+                Ok(SequenceFrame {
+                    code: Box::new(frame.code),
+                    payload: frame.payload,
+                })
+            }
+            SequenceSource::StructuredAppend(symbols) => {
+                let symbol = &symbols[index];
+                Ok(SequenceFrame {
+                    payload: symbol.data().to_vec(),
+                    code: Box::new(symbol.clone()),
+                })
+            }
+        }
+    }
+
+    // Position in an endless loop; fountain sequences emit fresh droplets on
+    // every pass instead of repeating the first one.
+    pub fn playback_frame(&self, position: usize) -> Result<SequenceFrame, Error> {
+        let index = position % self.len();
+        match &self.source {
+            SequenceSource::Frames(encoder)
+                if encoder.mode() == TransferMode::Fountain && index > 0 =>
+            {
+                let pass = position / self.len();
+                let seed = (pass * encoder.frame_count() + index - 1) % u32::MAX as usize;
+                let frame = encoder.frame(seed)?;
+                Ok(SequenceFrame {
+                    code: Box::new(frame.code),
+                    payload: frame.payload,
+                })
+            }
+            _ => self.frame(index),
+        }
+    }
+
+    pub fn frames(&self) -> Result<Vec<SequenceFrame>, Error> {
+        (0..self.len()).map(|index| self.frame(index)).collect()
+    }
+
+    pub fn code(&self, index: usize) -> Result<Box<dyn Matrix>, Error> {
+        Ok(self.frame(index)?.code)
+    }
+
+    pub fn codes(&self) -> Result<Vec<Box<dyn Matrix>>, Error> {
+        (0..self.len()).map(|index| self.code(index)).collect()
+    }
+}
+
+thread_local! {
+    static CURRENT_SEQUENCE: RefCell<Option<Rc<Sequence>>> = const { RefCell::new(None) };
+}
+
+pub fn set_current_sequence(sequence: Option<Sequence>) {
+    CURRENT_SEQUENCE.with(|current| *current.borrow_mut() = sequence.map(Rc::new));
+}
+
+pub fn current_sequence() -> Option<Rc<Sequence>> {
+    CURRENT_SEQUENCE.with(|current| current.borrow().clone())
+}
@@ -7,4 +7,4 @@ wasm-pack build ./qsc-generator \
     --release
 
 echo "Running server..."
-cargo run --release
+cargo run --release -p server